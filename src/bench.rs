@@ -0,0 +1,258 @@
+//! Benchmark mode, timing the parse, part 1 and part 2 phases of each solver.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cli::{self, BenchArgs},
+    solver::DynSolver,
+};
+
+/// Slow phases stop repeating after this much time, but always run at least once.
+const PHASE_BUDGET: Duration = Duration::from_secs(5);
+
+/// Wall times of the repeated runs of one phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Timing {
+    median: Duration,
+    min: Duration,
+    runs: usize,
+}
+
+/// Timings of one day, None for phases that panicked.
+struct DayTimings {
+    day: u8,
+    parse: Option<Timing>,
+    part1: Option<Timing>,
+    part2: Option<Timing>,
+}
+
+impl DayTimings {
+    fn phases(&self) -> [(&'static str, Option<Timing>); 3] {
+        [
+            ("parse", self.parse),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ]
+    }
+
+    /// Sum of the median times, or None if a phase failed.
+    fn total(&self) -> Option<Duration> {
+        self.phases()
+            .iter()
+            .map(|(_, timing)| timing.map(|timing| timing.median))
+            .sum()
+    }
+}
+
+/// Benchmarks the selected days. Returns false if any solver failed.
+pub fn bench(args: &BenchArgs) -> bool {
+    let solvers = match cli::select_solvers(args.day) {
+        Ok(solvers) => solvers,
+        Err(message) => {
+            eprintln!("{}", message);
+            return false;
+        }
+    };
+
+    let mut success = true;
+    let mut all_timings = Vec::new();
+    for solver in solvers {
+        match bench_day(solver.as_ref(), args) {
+            Ok(timings) => {
+                success &= timings.total().is_some();
+                if !args.markdown {
+                    print_timings(&timings);
+                }
+                all_timings.push(timings);
+            }
+            Err(error) => {
+                eprintln!("{}", error);
+                success = false;
+            }
+        }
+    }
+
+    if args.markdown {
+        print!("{}", markdown_table(&all_timings));
+    }
+    success
+}
+
+/// Times all phases of one day. Fails if there is no parsed input to time the parts with.
+///
+/// A part that panics is reported and has no timing, the other phases are still timed.
+/// Parsing is timed on the input of part 1.
+fn bench_day(solver: &dyn DynSolver, args: &BenchArgs) -> Result<DayTimings, String> {
+    let day = solver.day();
+    let input = cli::load_input(solver, 1, &args.input).map_err(|error| error.to_string())?;
+    let parsed = match cli::catch_panic(|| solver.parse(&input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(error)) => return Err(error.to_string()),
+        Err(message) => return Err(format!("Day {:02} failed: {}", day, message)),
+    };
+    let same_input =
+        cli::input_name(solver, 1, &args.input) == cli::input_name(solver, 2, &args.input);
+    let parsed_part2 = if same_input {
+        None
+    } else {
+        Some(cli::load_and_parse(solver, 2, &args.input)?)
+    };
+    let parsed_part2 = parsed_part2.as_ref().unwrap_or(&parsed);
+
+    let time_caught = |name: &str, phase: &dyn Fn()| {
+        cli::catch_panic(|| time_phase(args.runs, phase))
+            .map_err(|message| eprintln!("Day {:02} {} failed: {}", day, name, message))
+            .ok()
+    };
+    Ok(DayTimings {
+        day,
+        parse: time_caught("parse", &|| {
+            black_box(solver.parse(&input)).ok();
+        }),
+        part1: time_caught("part 1", &|| {
+            black_box(solver.part1(parsed.as_ref()));
+        }),
+        part2: time_caught("part 2", &|| {
+            black_box(solver.part2(parsed_part2.as_ref()));
+        }),
+    })
+}
+
+/// Runs the phase up to `runs` times and measures each run.
+fn time_phase(runs: usize, phase: impl Fn()) -> Timing {
+    let started = Instant::now();
+    let mut samples = Vec::with_capacity(runs);
+    while samples.len() < runs && (samples.is_empty() || started.elapsed() < PHASE_BUDGET) {
+        let start = Instant::now();
+        phase();
+        samples.push(start.elapsed());
+    }
+    summarize(samples)
+}
+
+fn summarize(mut samples: Vec<Duration>) -> Timing {
+    samples.sort();
+    Timing {
+        median: samples[samples.len() / 2],
+        min: samples[0],
+        runs: samples.len(),
+    }
+}
+
+fn print_timings(timings: &DayTimings) {
+    for (i, (name, timing)) in timings.phases().iter().enumerate() {
+        let day = if i == 0 {
+            format!("Day {:02}", timings.day)
+        } else {
+            String::new()
+        };
+        match timing {
+            Some(timing) => println!(
+                "{:6}  {:6}  median {:>10}  min {:>10}  ({} runs)",
+                day,
+                name,
+                format_duration(timing.median),
+                format_duration(timing.min),
+                timing.runs
+            ),
+            None => println!("{:6}  {:6}  failed", day, name),
+        }
+    }
+}
+
+/// Summary of all days, with median and min time of each phase.
+/// Days with a failed phase are left out of the total.
+fn markdown_table(all_timings: &[DayTimings]) -> String {
+    let cell = |timing: &Option<Timing>| match timing {
+        Some(timing) => format!(
+            "{} (min {})",
+            format_duration(timing.median),
+            format_duration(timing.min)
+        ),
+        None => "failed".to_string(),
+    };
+    let mut table = String::from("| Day | Parse | Part 1 | Part 2 | Total |\n");
+    table.push_str("|----:|------:|-------:|-------:|------:|\n");
+    let mut year_total = Duration::ZERO;
+    for timings in all_timings {
+        let total = timings.total();
+        year_total += total.unwrap_or_default();
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            timings.day,
+            cell(&timings.parse),
+            cell(&timings.part1),
+            cell(&timings.part2),
+            total.map_or("failed".to_string(), format_duration)
+        ));
+    }
+    table.push_str(&format!(
+        "| **Total** | | | | **{}** |\n",
+        format_duration(year_total)
+    ));
+    table
+}
+
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_samples() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+        assert_eq!(
+            summarize(samples),
+            Timing {
+                median: Duration::from_millis(3),
+                min: Duration::from_millis(1),
+                runs: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn leaves_failed_days_out_of_total() {
+        let timing = Some(summarize(vec![Duration::from_millis(2)]));
+        let timings = [
+            DayTimings {
+                day: 1,
+                parse: timing,
+                part1: None,
+                part2: timing,
+            },
+            DayTimings {
+                day: 2,
+                parse: timing,
+                part1: timing,
+                part2: timing,
+            },
+        ];
+        let table = markdown_table(&timings);
+        assert!(table
+            .contains("| 1 | 2.00 ms (min 2.00 ms) | failed | 2.00 ms (min 2.00 ms) | failed |"));
+        assert!(table.ends_with("| **Total** | | | | **6.00 ms** |\n"));
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+        assert_eq!(format_duration(Duration::from_secs(2)), "2.00 s");
+    }
+}
@@ -0,0 +1,371 @@
+//! Command line interface of the `aoc2023` binary.
+
+use std::{
+    any::Any,
+    error::Error,
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    time::Instant,
+};
+
+use crate::{
+    logging::LogSpec,
+    output::{self, OutputFormat, PartResult},
+    solver::{self, DynSolver},
+    utils,
+};
+
+pub const USAGE: &str =
+    "Usage: aoc2023 run [--day N] [--part 1|2] [--test | --input PATH] [--output text|json]
+                   [--log SPEC]
+       aoc2023 bench [--day N] [--runs N] [--markdown] [--test | --input PATH] [--log SPEC]
+
+run    Runs the solver for a single day, or for all registered days if --day is missing.
+bench  Times the parse, part 1 and part 2 phases of the selected days.
+
+Options:
+    --day N        Only run day N.
+    --part P       Only run part P (1 or 2) instead of both parts.
+    --test         Use the example input input/dayNN_test.txt.
+    --input PATH   Read the input from PATH instead, use - for stdin. Requires --day.
+    --output F     Print results in format F (text or json). json prints one line per part
+                   with day, part, answer, input and elapsed_ns. Defaults to text.
+    --runs N       Number of timed runs per phase, defaults to 20.
+    --markdown     Print the benchmark results as a markdown table.
+    --log SPEC     Show debug output of the solvers on stderr. SPEC is a level like debug,
+                   optionally followed by levels for single days, e.g. warn,12=trace.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    Help,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RunArgs {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub input: InputSource,
+    pub output: OutputFormat,
+    pub log: LogSpec,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchArgs {
+    pub day: Option<u8>,
+    pub input: InputSource,
+    pub runs: usize,
+    pub markdown: bool,
+    pub log: LogSpec,
+}
+
+/// Where the puzzle input of a day is read from.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The full puzzle input, `input/dayNN.txt`.
+    #[default]
+    Full,
+    /// The example input, `input/dayNN_test.txt`.
+    Test,
+    File(PathBuf),
+    Stdin,
+}
+
+/// Parses the command line arguments, without the program name.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        None => return Ok(Command::Help),
+        Some(command) => command,
+    };
+    match command.as_str() {
+        "help" | "--help" | "-h" => return Ok(Command::Help),
+        "run" | "bench" => {}
+        _ => return Err(format!("Unknown command: {}", command)),
+    }
+    let is_bench = command == "bench";
+
+    let mut run_args = RunArgs::default();
+    let mut runs = 20;
+    let mut markdown = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let day = value_of(&mut args, "--day")?;
+                run_args.day = Some(day.parse().map_err(|_| format!("Invalid day: {}", day))?);
+            }
+            "--part" if !is_bench => {
+                let part = value_of(&mut args, "--part")?;
+                match part.as_str() {
+                    "1" => run_args.part = Some(1),
+                    "2" => run_args.part = Some(2),
+                    _ => return Err(format!("Invalid part: {}", part)),
+                }
+            }
+            "--test" => run_args.input = InputSource::Test,
+            "--input" => {
+                let path = value_of(&mut args, "--input")?;
+                run_args.input = if path == "-" {
+                    InputSource::Stdin
+                } else {
+                    InputSource::File(PathBuf::from(path))
+                };
+            }
+            "--runs" if is_bench => {
+                let value = value_of(&mut args, "--runs")?;
+                runs = match value.parse() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(format!("Invalid number of runs: {}", value)),
+                };
+            }
+            "--markdown" if is_bench => markdown = true,
+            "--output" if !is_bench => {
+                run_args.output = OutputFormat::parse(&value_of(&mut args, "--output")?)?
+            }
+            "--log" => run_args.log = LogSpec::parse(&value_of(&mut args, "--log")?)?,
+            "--help" | "-h" => return Ok(Command::Help),
+            _ => return Err(format!("Unknown argument for {}: {}", command, arg)),
+        }
+    }
+
+    let explicit_input = matches!(run_args.input, InputSource::File(_) | InputSource::Stdin);
+    if explicit_input && run_args.day.is_none() {
+        return Err("--input requires --day".to_string());
+    }
+    if is_bench {
+        Ok(Command::Bench(BenchArgs {
+            day: run_args.day,
+            input: run_args.input,
+            runs,
+            markdown,
+            log: run_args.log,
+        }))
+    } else {
+        Ok(Command::Run(run_args))
+    }
+}
+
+fn value_of(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+/// The solver for the given day, or all registered solvers.
+pub fn select_solvers(day: Option<u8>) -> Result<Vec<Box<dyn DynSolver>>, String> {
+    match day {
+        Some(day) => match solver::find(day) {
+            Some(solver) => Ok(vec![solver]),
+            None => Err(format!("No solver for day {}", day)),
+        },
+        None => Ok(solver::registry()),
+    }
+}
+
+/// Runs the selected days and parts. Returns false if any solver failed.
+pub fn run(args: &RunArgs) -> bool {
+    let solvers = match select_solvers(args.day) {
+        Ok(solvers) => solvers,
+        Err(message) => {
+            eprintln!("{}", message);
+            return false;
+        }
+    };
+
+    let mut success = true;
+    for solver in solvers {
+        success &= run_day(solver.as_ref(), args);
+    }
+    success
+}
+
+/// Runs the selected parts of one day. Returns false if anything failed.
+///
+/// Solvers may still panic while solving. This is reported like any other error,
+/// so one broken part must not stop the other part or the other days.
+fn run_day(solver: &dyn DynSolver, args: &RunArgs) -> bool {
+    let day = solver.day();
+    let report = |part: Option<u8>, input_name: &str, error: String| {
+        eprintln!("{}", error);
+        if args.output == OutputFormat::Json {
+            println!("{}", output::json_error(day, part, input_name, &error));
+        }
+        false
+    };
+
+    let mut success = true;
+    // The parts share the parsed input, unless they have different examples.
+    let mut loaded: Option<(String, Option<Box<dyn Any>>)> = None;
+    for part in [1, 2] {
+        if args.part.is_some_and(|selected| selected != part) {
+            continue;
+        }
+        let input_name = input_name(solver, part, &args.input);
+        if loaded.as_ref().is_none_or(|(name, _)| *name != input_name) {
+            let parsed = load_and_parse(solver, part, &args.input)
+                .map_err(|error| success &= report(None, &input_name, error))
+                .ok();
+            loaded = Some((input_name.clone(), parsed));
+        }
+        let Some((_, Some(parsed))) = &loaded else {
+            continue;
+        };
+
+        let start = Instant::now();
+        let answer = catch_panic(|| match part {
+            1 => solver.part1(parsed.as_ref()),
+            _ => solver.part2(parsed.as_ref()),
+        });
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(message) => {
+                let error = format!("Day {:02} part {} failed: {}", day, part, message);
+                success &= report(Some(part), &input_name, error);
+                continue;
+            }
+        };
+        let result = PartResult {
+            day,
+            part,
+            answer: &answer,
+            input: &input_name,
+            elapsed: start.elapsed(),
+        };
+        println!("{}", result.format(args.output));
+    }
+    success
+}
+
+/// Loads the input for `part` and parses it, with parse panics as errors.
+pub(crate) fn load_and_parse(
+    solver: &dyn DynSolver,
+    part: u8,
+    source: &InputSource,
+) -> Result<Box<dyn Any>, String> {
+    let input = load_input(solver, part, source).map_err(|error| error.to_string())?;
+    match catch_panic(|| solver.parse(&input)) {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(message) => Err(format!("Day {:02} failed: {}", solver.day(), message)),
+    }
+}
+
+/// Runs `f` and returns the message if it panics.
+///
+/// The default panic hook is silenced meanwhile, the caller reports the message instead.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    panic::set_hook(hook);
+    result.map_err(|payload| panic_message(&*payload).to_string())
+}
+
+/// The message of a caught panic, if it has one.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Short name of the input of `part` for the output, like "day05_test.txt".
+pub(crate) fn input_name(solver: &dyn DynSolver, part: u8, source: &InputSource) -> String {
+    match source {
+        InputSource::Full => format!("day{:02}.txt", solver.day()),
+        InputSource::Test => solver.example(part),
+        InputSource::File(path) => path.display().to_string(),
+        InputSource::Stdin => "-".to_string(),
+    }
+}
+
+/// Reads the input of `part`, which only depends on the part for example inputs.
+pub fn load_input(
+    solver: &dyn DynSolver,
+    part: u8,
+    source: &InputSource,
+) -> Result<String, Box<dyn Error>> {
+    let day = solver.day();
+    let input = match source {
+        InputSource::Full => utils::read_day(day, false)?,
+        InputSource::Test => utils::read_day_file(day, &solver.example(part))?,
+        InputSource::File(path) => utils::read_path(path)?,
+        InputSource::Stdin => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            utils::normalize(&input)
+        }
+    };
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn parses_run_with_all_options() {
+        assert_eq!(
+            parse("run --day 5 --part 2 --input input/day05_test.txt"),
+            Ok(Command::Run(RunArgs {
+                day: Some(5),
+                part: Some(2),
+                input: InputSource::File(PathBuf::from("input/day05_test.txt")),
+                output: OutputFormat::Text,
+                log: LogSpec::default(),
+            }))
+        );
+        assert_eq!(
+            parse("run --test"),
+            Ok(Command::Run(RunArgs {
+                input: InputSource::Test,
+                ..RunArgs::default()
+            }))
+        );
+        assert_eq!(
+            parse("bench --day 12 --runs 3 --markdown"),
+            Ok(Command::Bench(BenchArgs {
+                day: Some(12),
+                input: InputSource::Full,
+                runs: 3,
+                markdown: true,
+                log: LogSpec::default(),
+            }))
+        );
+    }
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+        assert_eq!(
+            catch_panic(|| panic!("Broken")),
+            Err::<(), _>("Broken".to_string())
+        );
+        let day = 7;
+        assert_eq!(
+            catch_panic(|| panic!("Day {} is broken", day)),
+            Err::<(), _>("Day 7 is broken".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(parse("run --part 3").is_err());
+        assert!(parse("run --day").is_err());
+        assert!(parse("run --input -").is_err());
+        assert!(parse("walk").is_err());
+        assert!(parse("run --runs 3").is_err());
+        assert!(parse("bench --part 1").is_err());
+        assert!(parse("bench --runs 0").is_err());
+        assert!(parse("bench --output json").is_err());
+        assert!(parse("run --output xml").is_err());
+    }
+}
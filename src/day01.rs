@@ -1,22 +1,68 @@
+use crate::{
+    error::{input_lines, ParseError},
+    solver::Solver,
+};
 
-pub fn part01(input: &str) -> u32 {
-    let mut total: u32 = 0;
-    for line in input.lines() {
-        total += decode_into_number(line, starting_digit) as u32;
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = Vec<CalibrationLine>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<CalibrationLine>, ParseError> {
+        parse_day01(input)
+    }
+
+    fn part1(lines: &Vec<CalibrationLine>) -> u32 {
+        let mut total: u32 = 0;
+        for (index, line) in lines.iter().enumerate() {
+            // The example for part 2 has lines with only spelled out digits.
+            let Some(value) = line.digits else {
+                panic!(
+                    "Line {} has no digit, it only has a value in part 2",
+                    index + 1
+                );
+            };
+            total += value as u32;
+        }
+        total
+    }
+
+    fn part2(lines: &Vec<CalibrationLine>) -> u32 {
+        lines.iter().map(|line| line.digits_and_words as u32).sum()
     }
-    total
 }
 
-pub fn part02(input: &str) -> u32 {
-    let mut total: u32 = 0;
-    for line in input.lines() {
-        total += decode_into_number(line, starting_digit_allow_text) as u32;
+/// The calibration value of a line, built from its first and last digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationLine {
+    /// Only counting digits, or None if the line has none.
+    pub digits: Option<u8>,
+    /// Also counting spelled out digits like "one".
+    pub digits_and_words: u8,
+}
+
+pub fn parse_day01(input: &str) -> Result<Vec<CalibrationLine>, ParseError> {
+    let mut lines = Vec::new();
+    for line in input_lines(1, input) {
+        if let Some(i) = line.text.chars().position(|c| !c.is_ascii_alphanumeric()) {
+            return Err(line.error_at(i, "Invalid character"));
+        }
+        let Some(digits_and_words) = decode_into_number(line.text, starting_digit_allow_text)
+        else {
+            return Err(line.error(line.text, "Expected at least one digit"));
+        };
+        lines.push(CalibrationLine {
+            digits: decode_into_number(line.text, starting_digit),
+            digits_and_words,
+        });
     }
-    total
+    Ok(lines)
 }
 
-/// Decodes a line into a number using the given decoder
-fn decode_into_number(line: &str, decoder: impl Fn(&str) -> Option<u8>) -> u8 {
+/// Decodes a line into a number using the given decoder, or None if it has no digit.
+fn decode_into_number(line: &str, decoder: impl Fn(&str) -> Option<u8>) -> Option<u8> {
     let mut first_digit = None;
     let mut last_digit = None;
     for i in 0..line.len() {
@@ -28,8 +74,7 @@ fn decode_into_number(line: &str, decoder: impl Fn(&str) -> Option<u8>) -> u8 {
             last_digit = Some(digit);
         }
     }
-    // Since this is AOC, we are allowed to just unwrap and can assume this to be valid.
-    first_digit.unwrap() * 10 + last_digit.unwrap()
+    Some(first_digit? * 10 + last_digit?)
 }
 
 /// Checks if the slice starts with a digit and returns it.
@@ -68,3 +113,29 @@ fn starting_digit_allow_text(slice: &str) -> Option<u8> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day01_test() {
+        let input = read_file("day01.txt").unwrap();
+        assert_eq!(Day01::solve_part1(input.as_str()), Ok(54388));
+        assert_eq!(Day01::solve_part2(input.as_str()), Ok(53515));
+        let input = read_file("day01_test_part1.txt").unwrap();
+        assert_eq!(Day01::solve_part1(input.as_str()), Ok(142));
+        let input = read_file("day01_test.txt").unwrap();
+        assert_eq!(Day01::solve_part2(input.as_str()), Ok(281));
+    }
+
+    #[test]
+    fn rejects_lines_without_digits() {
+        let error = parse_day01("1abc2\nabc").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "Expected at least one digit");
+        let error = parse_day01("1a c2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::{
+    error::{input_lines, InputLine, ParseError},
+    solver::Solver,
+};
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<(i32, i32, i32, i32)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<(i32, i32, i32, i32)>, ParseError> {
+        input_lines(2, input).map(get_color_maxes).collect()
+    }
+
+    fn part1(games: &Vec<(i32, i32, i32, i32)>) -> i32 {
+        let mut index_total = 0;
+        for &(i, r, g, b) in games {
+            if r <= 12 && g <= 13 && b <= 14 {
+                index_total += i;
+            }
+        }
+        index_total
+    }
+
+    fn part2(games: &Vec<(i32, i32, i32, i32)>) -> i32 {
+        let mut total_power = 0;
+        for &(_, r, g, b) in games {
+            total_power += r * g * b;
+        }
+        total_power
+    }
+}
+
+/// Parses a line of the input file. Example:
+/// Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+pub fn get_color_maxes(line: InputLine) -> Result<(i32, i32, i32, i32), ParseError> {
+    // Both regexes are compiled once, not for every line.
+    static GAME_RE: OnceLock<Regex> = OnceLock::new();
+    static CUBE_RE: OnceLock<Regex> = OnceLock::new();
+    let re = GAME_RE.get_or_init(|| Regex::new(r"^Game ([0-9]+): (.*)$").unwrap());
+    let Some(caps) = re.captures(line.text) else {
+        return Err(line.error(line.text, "Expected \"Game N: ...\""));
+    };
+    let game_number = caps.get(1).unwrap().as_str();
+    let second_part = caps.get(2).unwrap().as_str();
+
+    // Aggregate maximum number of each color
+    let mut max_red = 0;
+    let mut max_green = 0;
+    let mut max_blue = 0;
+
+    // Parse cubes with regex: ([0-9]+) (red|green|blue)
+    let cube_re = CUBE_RE.get_or_init(|| Regex::new(r"^ ?([0-9]+) (red|green|blue)$").unwrap());
+
+    // Split the second part by "; " to get the rounds
+    let rounds = second_part.split(';');
+    // For each round, split by ", " to get the cubes
+    for round in rounds {
+        let mut red = 0;
+        let mut green = 0;
+        let mut blue = 0;
+
+        let cubes = round.split(", ");
+        for cube in cubes {
+            let Some(caps) = cube_re.captures(cube) else {
+                return Err(line.error(cube, "Expected a number and red, green or blue"));
+            };
+            let number = line.parse::<i32>(caps.get(1).unwrap().as_str())?;
+            let color = caps.get(2).unwrap().as_str();
+
+            match color {
+                "red" => red += number,
+                "green" => green += number,
+                "blue" => blue += number,
+                _ => unreachable!("The regex only matches known colors"),
+            }
+        }
+        if red > max_red {
+            max_red = red;
+        }
+        if green > max_green {
+            max_green = green;
+        }
+        if blue > max_blue {
+            max_blue = blue;
+        }
+    }
+    Ok((line.parse(game_number)?, max_red, max_green, max_blue))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day02_test() {
+        let input = read_file("day02_test.txt").unwrap();
+        assert_eq!(Day02::solve_part1(&input), Ok(8));
+        assert_eq!(Day02::solve_part2(&input), Ok(2286));
+    }
+}
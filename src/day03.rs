@@ -1,19 +1,39 @@
 use std::collections::HashMap;
 
-pub fn both_parts(input: &str) -> (u64, u64) {
-    let symbol_map = scan_symbols(input);
+use crate::{error::ParseError, solver::Solver, utils::Grid};
 
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Input = Grid<char>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(3, input, Some)
+    }
+
+    fn part1(schematic: &Grid<char>) -> u64 {
+        both_parts(schematic).0
+    }
+
+    fn part2(schematic: &Grid<char>) -> u64 {
+        both_parts(schematic).1
+    }
+}
+
+pub fn both_parts(schematic: &Grid<char>) -> (u64, u64) {
     // Each entry is (product, count) with a default of (1, 0) for empty spaces
     let mut gear_ratio_map: HashMap<(i32, i32), (u64, u64)> = HashMap::new();
 
     let mut accumulator = 0;
 
-    for (y, line) in input.lines().enumerate() {
+    for (y, line) in schematic.rows().enumerate() {
         let mut number = ScannedNumber::new();
-        for (x, c) in line.chars().enumerate() {
+        for (x, c) in line.iter().enumerate() {
             // Do we see a digit?
             if let Some(digit) = c.to_digit(10) {
-                number.extend(digit, x, y, &symbol_map);
+                number.extend(digit, x, y, schematic);
             } else {
                 if number.is_part {
                     // Track number in accumulator
@@ -40,7 +60,7 @@ pub fn both_parts(input: &str) -> (u64, u64) {
     let part2: u64 = gear_ratio_map
         .iter()
         .filter(|(_, (_, count))| *count == 2)
-        .filter(|(coord, _)| symbol_map.get(coord) == Some(&'*'))
+        .filter(|(coord, _)| schematic.get(**coord) == Some(&'*'))
         .map(|(_, (product, _))| product)
         .sum();
 
@@ -70,7 +90,7 @@ impl ScannedNumber {
         self.number > 0
     }
 
-    fn extend(&mut self, digit: u32, x: usize, y: usize, symbol_map: &HashMap<(i32, i32), char>) {
+    fn extend(&mut self, digit: u32, x: usize, y: usize, schematic: &Grid<char>) {
         // Is this the first digit?
         if self.number == 0 {
             self.left_x = x as i32;
@@ -78,7 +98,7 @@ impl ScannedNumber {
         self.number = self.number * 10 + digit as u64;
         self.right_x = x as i32;
         self.y = y as i32;
-        self.is_part = self.is_part || symbol_in_nbhd(symbol_map, x as i32, y as i32);
+        self.is_part = self.is_part || symbol_in_nbhd(schematic, (x as i32, y as i32));
     }
 
     fn update_gear_ratio_map(&self, gear_ratio_map: &mut HashMap<(i32, i32), (u64, u64)>) {
@@ -95,31 +115,29 @@ impl ScannedNumber {
     }
 }
 
-fn scan_symbols(input: &str) -> HashMap<(i32, i32), char> {
-    let mut symbol_map: HashMap<(i32, i32), char> = HashMap::new();
-
-    // Scan input to put all symbols into a map
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if !"0123456789.".contains(c) {
-                symbol_map.insert((x as i32, y as i32), c);
-            }
-        }
-    }
+fn is_symbol(c: char) -> bool {
+    !"0123456789.".contains(c)
+}
 
-    symbol_map
+fn symbol_in_nbhd(schematic: &Grid<char>, position: (i32, i32)) -> bool {
+    schematic
+        .neighbours8(position)
+        .any(|neighbour| schematic.get(neighbour).is_some_and(|c| is_symbol(*c)))
 }
 
-fn symbol_in_nbhd(symbol_map: &HashMap<(i32, i32), char>, x: i32, y: i32) -> bool {
-    for dx in -1..=1 {
-        for dy in -1..=1 {
-            if dx == 0 && dy == 0 {
-                continue;
-            }
-            if symbol_map.contains_key(&(x + dx, y + dy)) {
-                return true;
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day03_test() {
+        let input = read_file("day03_test.txt").unwrap();
+        assert_eq!(both_parts(&Day03::parse(&input).unwrap()), (4361, 467835));
+        let input = read_file("day03.txt").unwrap();
+        assert_eq!(
+            both_parts(&Day03::parse(&input).unwrap()),
+            (532428, 84051670)
+        );
     }
-    false
 }
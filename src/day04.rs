@@ -0,0 +1,167 @@
+use log::{debug, trace};
+
+use crate::{
+    error::{input_lines, ParseError},
+    solver::Solver,
+};
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Input = Vec<ScratchCard>;
+    type Answer1 = i32;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Vec<ScratchCard>, ParseError> {
+        parse_day04(input)
+    }
+
+    fn part1(cards: &Vec<ScratchCard>) -> i32 {
+        day04_part1(cards)
+    }
+
+    fn part2(cards: &Vec<ScratchCard>) -> u128 {
+        day04_part2(cards)
+    }
+}
+
+/// A card number together with the count of owned numbers that are winning.
+pub struct ScratchCard {
+    pub card_number: usize,
+    pub matches: u32,
+}
+
+pub fn parse_day04(input: &str) -> Result<Vec<ScratchCard>, ParseError> {
+    // Parsing a line of the input file. Example:
+    // Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+
+    let mut cards = Vec::new();
+
+    // Split along ":" and "|"
+    for line in input_lines(4, input) {
+        let parts: Vec<&str> = line.text.split([':', '|'].as_ref()).collect();
+
+        trace!("Parts: {:?}", parts);
+        if parts.len() != 3 {
+            return Err(line.error(line.text, "Expected \"Card N: winning | owned\""));
+        }
+        // Cut off "Card " from the first part
+        let Some(card_number) = parts[0].strip_prefix("Card ") else {
+            return Err(line.error(parts[0], "Expected \"Card \""));
+        };
+        let card_number = line.parse::<usize>(card_number)?;
+        trace!("Card number: {}", card_number);
+
+        // Split the second part by " " and parse them as numbers
+        let winning: Vec<i32> = parts[1]
+            .split(' ')
+            .filter(|s| !s.is_empty())
+            .map(|s| line.parse::<i32>(s))
+            .collect::<Result<_, _>>()?;
+        trace!("Numbers: {:?}", winning);
+
+        // Split the third part by " " and parse them as numbers
+        let owned: Vec<i32> = parts[2]
+            .split(' ')
+            .filter(|s| !s.is_empty())
+            .map(|s| line.parse::<i32>(s))
+            .collect::<Result<_, _>>()?;
+
+        trace!("Owned: {:?}", owned);
+
+        // count how many of the owned numbers are in the winning numbers
+        let mut count = 0;
+        for number in owned {
+            if winning.contains(&number) {
+                count += 1;
+            }
+        }
+        debug!("Count: {}", count);
+        cards.push(ScratchCard {
+            card_number,
+            matches: count,
+        });
+    }
+    Ok(cards)
+}
+
+pub fn day04_part1(cards: &[ScratchCard]) -> i32 {
+    let mut total_value = 0;
+    for card in cards {
+        // The value of a card is count 0 => 0, count 1 => 1, count 2 => 2, count 3 => 4, count 4 => 8, ...
+
+        // Calculate the value of the card
+        let value = if card.matches > 0 {
+            2_i32.pow(card.matches - 1)
+        } else {
+            0
+        };
+        debug!("Value: {}", value);
+
+        // Add the value to the total value
+        total_value += value;
+    }
+    total_value
+}
+
+pub fn day04_part2(cards: &[ScratchCard]) -> u128 {
+    // Cards can win copies of cards after the last one, which are never owned.
+    let size = cards
+        .iter()
+        .map(|card| card.card_number + card.matches as usize + 1)
+        .max()
+        .unwrap_or(1);
+    let mut matches_on_card = vec![0_u32; size];
+    let mut card_count = vec![0_u32; size];
+    for card in cards {
+        matches_on_card[card.card_number] = card.matches;
+        card_count[card.card_number] = 1;
+    }
+
+    let mut recursive_card_production = vec![0_u128; size];
+
+    // Calculate part two solution.
+    // Reverse iterate over the cards
+    let mut index = size - 1;
+
+    while index > 0 {
+        // If the card is not owned, skip it
+        if card_count[index] == 0 {
+            index -= 1;
+            continue;
+        }
+
+        // Count the recursive card production of cards below this card.
+        recursive_card_production[index] = 1; // The card itself
+        for i in (index + 1)..=(index + matches_on_card[index] as usize) {
+            recursive_card_production[index] += recursive_card_production[i];
+        }
+
+        index -= 1;
+    }
+
+    debug!("Recursive card production: {:?}", recursive_card_production);
+    recursive_card_production.iter().sum::<u128>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day04_test() {
+        let input = read_file("day04_test.txt").unwrap();
+        assert_eq!(Day04::solve_part1(&input), Ok(13));
+        assert_eq!(Day04::solve_part2(&input), Ok(30));
+    }
+
+    #[test]
+    fn handles_large_card_numbers() {
+        assert_eq!(Day04::solve_part2("Card 300: 1 | 1"), Ok(1));
+        assert_eq!(
+            Day04::solve_part2("Card 299: 1 2 | 1 2\nCard 300: 1 | 1"),
+            Ok(3)
+        );
+    }
+}
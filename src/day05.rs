@@ -0,0 +1,324 @@
+use std::cmp::{max, min};
+
+use log::{debug, trace};
+
+use crate::{
+    error::{input_lines, InputLine, ParseError},
+    solver::Solver,
+};
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input = Almanac;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Almanac, ParseError> {
+        parse_day05(input)
+    }
+
+    fn part1(almanac: &Almanac) -> u128 {
+        solve_day05_part1(almanac)
+    }
+
+    fn part2(almanac: &Almanac) -> u128 {
+        solve_day05_part2(almanac)
+    }
+}
+
+/// Seeds and the seven maps from seed to location, in order.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u128>,
+    pub maps: Vec<AdventMap>,
+}
+
+/// The headers of all maps, in the order they are applied.
+pub const ALMANAC_MAP_HEADERS: [&str; 7] = [
+    "seed-to-soil map:",
+    "soil-to-fertilizer map:",
+    "fertilizer-to-water map:",
+    "water-to-light map:",
+    "light-to-temperature map:",
+    "temperature-to-humidity map:",
+    "humidity-to-location map:",
+];
+
+pub fn parse_day05(input: &str) -> Result<Almanac, ParseError> {
+    let missing =
+        |text: &str, message: &str| ParseError::new(5, input.lines().count(), 0, text, message);
+    let mut lines = input_lines(5, input);
+    // The first line is for seeds:
+    // seeds: 79 14 55 13
+    let seed_line = lines.next().ok_or_else(|| missing("", "Missing seeds"))?;
+    let Some(seed_numbers) = seed_line.text.strip_prefix("seeds: ") else {
+        return Err(seed_line.error(seed_line.text, "Expected \"seeds: \""));
+    };
+    let seeds = seed_numbers
+        .split(' ')
+        .map(|s| seed_line.parse::<u128>(s))
+        .collect::<Result<Vec<u128>, ParseError>>()?;
+    // In part 2 every second number is the length of a range.
+    let zero_length = seeds.chunks(2).position(|pair| pair.get(1) == Some(&0));
+    if let Some(pair) = zero_length {
+        let length = seed_numbers.split(' ').nth(pair * 2 + 1).unwrap();
+        return Err(seed_line.error(length, "Seed range length must not be zero"));
+    }
+    debug!("Seeds: {:?}", seeds);
+
+    let mut maps = Vec::new();
+    for header in ALMANAC_MAP_HEADERS {
+        // Discard lines up to (and including) the header line, e.g. "seed-to-soil map:"
+        loop {
+            let line = lines.next().ok_or_else(|| missing(header, "Missing map"))?;
+            if line.text == header {
+                break;
+            }
+        }
+        maps.push(parse_map_until_blank(&mut lines)?);
+    }
+
+    // All maps parsed. Now we can start calculating.
+    trace!("Seed to soil: {:?}", maps[0]);
+
+    Ok(Almanac { seeds, maps })
+}
+
+pub fn solve_day05_part1(almanac: &Almanac) -> u128 {
+    // For each seed, map though all the maps, producing an output line like:
+    // Seed 79, soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78, location 82.
+    let mut lowest_location = u128::MAX;
+
+    for &seed in &almanac.seeds {
+        let mut location = seed;
+        for map in &almanac.maps {
+            location = map.map(location);
+        }
+        debug!("Seed {}, location {}.", seed, location);
+
+        if location < lowest_location {
+            lowest_location = location;
+        }
+    }
+
+    lowest_location
+}
+
+pub fn solve_day05_part2(almanac: &Almanac) -> u128 {
+    // Part 2: Same again, but use ranges this time.
+    // The values on the initial seeds: line come in pairs. Within each pair, the first value is the start of the range and the second value is the length of the range.
+    // 79 14 55 13 -> (79, 14), (55, 13)
+    let seeds = &almanac.seeds;
+    let mut seed_ranges = Vec::new();
+    for i in 0..seeds.len() / 2 {
+        seed_ranges.push((seeds[i * 2], seeds[i * 2 + 1]));
+    }
+    debug!("Seed ranges: {:?}", seed_ranges);
+
+    // Flat Map thought all the maps, outputting along the way
+    let mut ranges = seed_ranges;
+    for (map, header) in almanac.maps.iter().zip(ALMANAC_MAP_HEADERS) {
+        ranges = ranges
+            .iter()
+            .flat_map(|(start, length)| map.map_range((*start, *length)))
+            .collect::<Vec<(u128, u128)>>();
+        trace!("Ranges after {} {:?}", header, ranges);
+    }
+
+    // Find the lowest location
+    let mut lowest_location = u128::MAX;
+    for (start, _length) in ranges {
+        if start < lowest_location {
+            lowest_location = start;
+        }
+    }
+    lowest_location
+}
+
+pub fn parse_map_until_blank<'a>(
+    lines: &mut impl Iterator<Item = InputLine<'a>>,
+) -> Result<AdventMap, ParseError> {
+    // Parse and advent mapping
+    // [destination] [sourge range start] [source range length]
+    // 2824905526 2969131334 898611144
+    // 0 322319732 9776277
+    // Do this until you reach an empty line
+    let mut seed_to_soil = AdventMap::default();
+    for line in lines.by_ref() {
+        if line.text.is_empty() {
+            break;
+        }
+        let numbers: Vec<&str> = line.text.split(' ').collect();
+        let parts = numbers
+            .iter()
+            .map(|s| line.parse::<u128>(s))
+            .collect::<Result<Vec<u128>, ParseError>>()?;
+        if parts.len() != 3 {
+            return Err(line.error(line.text, "Expected three numbers"));
+        }
+        if parts[2] == 0 {
+            return Err(line.error(numbers[2], "Range length must not be zero"));
+        }
+        seed_to_soil.map_sections.push(MapSection {
+            destination: parts[0],
+            source_range_start: parts[1],
+            source_range_length: parts[2],
+        });
+    }
+    seed_to_soil
+        .map_sections
+        .sort_by_key(|s| s.source_range_start);
+
+    Ok(seed_to_soil)
+}
+
+/// A map section like "50 98 2" means, that 98 -> 50, 99 -> 51.
+#[derive(Debug)]
+pub struct MapSection {
+    pub destination: u128,
+    pub source_range_start: u128,
+    pub source_range_length: u128,
+}
+
+#[derive(Default, Debug)]
+pub struct AdventMap {
+    pub map_sections: Vec<MapSection>,
+}
+
+impl AdventMap {
+    pub fn map(&self, source: u128) -> u128 {
+        // Find the map section that contains the source
+        let mut index = 0;
+        while index < self.map_sections.len()
+            && self.map_sections[index].source_range_start
+                + self.map_sections[index].source_range_length
+                - 1
+                < source
+        {
+            index += 1;
+        }
+        if index == self.map_sections.len() {
+            return source; // No mapping defined
+        }
+        // println!("Index: {}, mappiing {:?}", index, self.map_sections[index]);
+        if self.map_sections[index].source_range_start
+            + self.map_sections[index].source_range_length
+            > source
+            && self.map_sections[index].source_range_start <= source
+        {
+            return (self.map_sections[index].destination + source)
+                - self.map_sections[index].source_range_start;
+        }
+        source // No mapping defined
+    }
+
+    /// Ranges may map to multiple ranges, if they overlap with more than one map section.
+    pub fn map_range(&self, interval: (u128, u128)) -> Vec<(u128, u128)> {
+        let mut result = Vec::new();
+
+        let mut remaining_interval = interval;
+        for section in &self.map_sections {
+            let (left_interval, intersecting_interval, new_remaining_interval) =
+                map_one_section(section, remaining_interval);
+
+            result.extend(left_interval);
+            result.extend(intersecting_interval);
+
+            if let Some(new_remaining_interval) = new_remaining_interval {
+                remaining_interval = new_remaining_interval;
+            } else {
+                return result;
+            }
+        }
+
+        result.push(remaining_interval);
+
+        result
+    }
+}
+
+/// An interval split by a map section, see `map_one_section`.
+type SectionSplit = (
+    Option<(u128, u128)>,
+    Option<(u128, u128)>,
+    Option<(u128, u128)>,
+);
+
+/// Takes a section and an interval and returns 0-2 intervals in the first two options and 0 or 1 unmapped intervals in the third option.
+/// result.0 - interval fully before the section, already remapped (no-op).
+/// result.1 - interval fully inside the section, already remapped by section.
+/// result.2 - interval fully after the section, may be mapped by other sections.
+fn map_one_section(section: &MapSection, (start, length): (u128, u128)) -> SectionSplit {
+    // Is there a first interval?
+    let left_interval = if start < section.source_range_start {
+        let left = start;
+        let right = min(start + length - 1, section.source_range_start - 1);
+        Some((left, right - left + 1))
+    } else {
+        None
+    };
+
+    // Is there a second interval?
+    let intersecting_interval = if start + length > section.source_range_start
+        && start < section.source_range_start + section.source_range_length
+    {
+        // There is some overlap
+        let left = max(start, section.source_range_start);
+        let right = min(
+            start + length - 1,
+            section.source_range_start + section.source_range_length - 1,
+        );
+        Some((left, right - left + 1))
+    } else {
+        None
+    };
+
+    // Remap the intersecting interval, if there is one
+    let intersecting_interval = if let Some((left, length)) = intersecting_interval {
+        let left = (section.destination + left) - section.source_range_start;
+        Some((left, length))
+    } else {
+        None
+    };
+
+    // Is there a third interval?
+    let new_remaining_interval =
+        if start + length - 1 > section.source_range_start + section.source_range_length - 1 {
+            let left = max(
+                start,
+                section.source_range_start + section.source_range_length,
+            );
+            let right = start + length - 1;
+            Some((left, right - left + 1))
+        } else {
+            None
+        };
+
+    (left_interval, intersecting_interval, new_remaining_interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day05_test() {
+        let input = read_file("day05_test.txt").unwrap();
+        assert_eq!(Day05::solve_part1(&input), Ok(35));
+        assert_eq!(Day05::solve_part2(&input), Ok(46));
+    }
+
+    #[test]
+    fn rejects_zero_lengths() {
+        let input = read_file("day05_test.txt").unwrap();
+        let error = parse_day05(&input.replace("seeds: 79 14", "seeds: 79 0")).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
+        assert_eq!(error.message, "Seed range length must not be zero");
+
+        let error = parse_day05(&input.replace("50 98 2", "50 98 0")).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 7));
+        assert_eq!(error.message, "Range length must not be zero");
+    }
+}
@@ -1,15 +1,59 @@
-pub fn solve_day07(input: &str, with_joker: bool) -> u128 {
+use log::{debug, trace};
+
+use crate::{
+    error::{input_lines, ParseError},
+    solver::Solver,
+};
+
+pub struct Day07;
+
+impl Solver for Day07 {
+    type Input = Vec<(String, u128)>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Vec<(String, u128)>, ParseError> {
+        parse_day07(input)
+    }
+
+    fn part1(hands: &Vec<(String, u128)>) -> u128 {
+        solve_day07(hands, false)
+    }
+
+    fn part2(hands: &Vec<(String, u128)>) -> u128 {
+        solve_day07(hands, true)
+    }
+}
+
+/// Parses lines like "32T3K 765" into the hand and the bid.
+pub fn parse_day07(input: &str) -> Result<Vec<(String, u128)>, ParseError> {
+    let mut hands = vec![];
+    for line in input_lines(7, input) {
+        let Some((hand, bid)) = line.text.split_once(' ') else {
+            return Err(line.error(line.text, "Expected a hand and a bid"));
+        };
+        if hand.chars().count() != 5 {
+            return Err(line.error(hand, "Expected a hand of 5 cards"));
+        }
+        for (i, card) in hand.chars().enumerate() {
+            if card_to_ordinal(card, false).is_none() {
+                return Err(line.error_at(i, "Card not recognized"));
+            }
+        }
+        hands.push((hand.to_string(), line.parse::<u128>(bid)?));
+    }
+    Ok(hands)
+}
+
+pub fn solve_day07(hands: &[(String, u128)], with_joker: bool) -> u128 {
     let mut all_hands = vec![];
-    for line in input.lines() {
-        let parts: Vec<&str> = line.split(' ').collect();
-        let hand = parts[0];
-        let bid = parts[1].parse::<u128>().unwrap();
-        all_hands.push(Hand::new(hand.to_string(), bid, with_joker));
+    for (hand, bid) in hands {
+        all_hands.push(Hand::new(hand.clone(), *bid, with_joker));
     }
     all_hands.sort_by_key(|h| h.integer_proxy);
     // println!("All hands: {:?}", all_hands);
     for hand in &all_hands {
-        println!("Hand: {:?}", hand);
+        trace!("Hand: {:?}", hand);
     }
 
     let mut total_winning = 0;
@@ -17,14 +61,16 @@ pub fn solve_day07(input: &str, with_joker: bool) -> u128 {
         total_winning += hand.bid * (i + 1) as u128;
     }
 
-    println!("Total winning: {}", total_winning);
+    debug!("Total winning: {}", total_winning);
     total_winning
 }
 
 #[derive(Debug)]
 struct Hand {
+    #[allow(dead_code)] // Only shown in the debug output
     cards: String,
     bid: u128,
+    #[allow(dead_code)] // Only shown in the debug output
     hand_type: HandType,
     integer_proxy: u64,
 }
@@ -44,13 +90,18 @@ impl Hand {
     fn hand_to_integer_proxy(cards: String, hand_type: HandType, with_joker: bool) -> u64 {
         // Builds an integer that, looking at the bits is:
         // 3 bits for hand type, 4 bits for each cards from left to right in cards.
-        let chars = cards.chars().collect::<Vec<char>>();
+        let ordinals = cards
+            .chars()
+            .map(|card| {
+                card_to_ordinal(card, with_joker).expect("Cards are validated while parsing") as u64
+            })
+            .collect::<Vec<u64>>();
         hand_type as u64 * 2_u64.pow(20)
-            + card_to_ordinal(chars[0], with_joker) as u64 * 2_u64.pow(16)
-            + card_to_ordinal(chars[1], with_joker) as u64 * 2_u64.pow(12)
-            + card_to_ordinal(chars[2], with_joker) as u64 * 2_u64.pow(8)
-            + card_to_ordinal(chars[3], with_joker) as u64 * 2_u64.pow(4)
-            + card_to_ordinal(chars[4], with_joker) as u64
+            + ordinals[0] * 2_u64.pow(16)
+            + ordinals[1] * 2_u64.pow(12)
+            + ordinals[2] * 2_u64.pow(8)
+            + ordinals[3] * 2_u64.pow(4)
+            + ordinals[4]
     }
 }
 
@@ -65,8 +116,8 @@ enum HandType {
     HighCard = 0,
 }
 
-fn card_to_ordinal(card: char, with_joker: bool) -> u8 {
-    match card {
+fn card_to_ordinal(card: char, with_joker: bool) -> Option<u8> {
+    let ordinal = match card {
         '2' => 2,
         '3' => 3,
         '4' => 4,
@@ -86,8 +137,9 @@ fn card_to_ordinal(card: char, with_joker: bool) -> u8 {
         'Q' => 12,
         'K' => 13,
         'A' => 14,
-        _ => panic!("Card {} not recognized", card),
-    }
+        _ => return None,
+    };
+    Some(ordinal)
 }
 
 /// Every hand is a string of 5 cards, like "32T3K".
@@ -95,7 +147,8 @@ fn card_to_ordinal(card: char, with_joker: bool) -> u8 {
 fn classify_hand_type(cards: String, with_joker: bool) -> HandType {
     let mut count_by_ordinal: [u8; 15] = [0; 15];
     for card in cards.chars() {
-        count_by_ordinal[card_to_ordinal(card, with_joker) as usize] += 1;
+        let ordinal = card_to_ordinal(card, with_joker).expect("Cards are validated while parsing");
+        count_by_ordinal[ordinal as usize] += 1;
     }
 
     let joker_count = count_by_ordinal[1];
@@ -104,9 +157,9 @@ fn classify_hand_type(cards: String, with_joker: bool) -> HandType {
     // Then add the joker count to it.
     let mut max_count = 0;
     let mut max_count_i = 0;
-    for i in 2..=14 {
-        if count_by_ordinal[i] > max_count {
-            max_count = count_by_ordinal[i];
+    for (i, &count) in count_by_ordinal.iter().enumerate().skip(2) {
+        if count > max_count {
+            max_count = count;
             max_count_i = i;
         }
     }
@@ -150,15 +203,22 @@ mod tests {
 
     #[test]
     fn test_day07_part1() {
-        let input = utils::read_file("day07.txt");
-        let result = solve_day07(&input, false);
+        let hands = parse_day07(&utils::read_file("day07.txt").unwrap()).unwrap();
+        let result = solve_day07(&hands, false);
         assert_eq!(result, 249638405);
     }
 
     #[test]
     fn test_day07_part2() {
-        let input = utils::read_file("day07.txt");
-        let result = solve_day07(&input, true);
+        let hands = parse_day07(&utils::read_file("day07.txt").unwrap()).unwrap();
+        let result = solve_day07(&hands, true);
         assert_eq!(result, 249776650);
     }
+
+    #[test]
+    fn test_day07_invalid_card() {
+        let error = parse_day07("32T3K 765\nT55X5 684").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.text, "X");
+    }
 }
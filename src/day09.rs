@@ -0,0 +1,218 @@
+use log::trace;
+
+use crate::{
+    error::{input_lines, ParseError},
+    solver::Solver,
+};
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Input = Vec<Vec<i128>>;
+    type Answer1 = i128;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i128>>, ParseError> {
+        parse_day09(input)
+    }
+
+    fn part1(rows: &Vec<Vec<i128>>) -> i128 {
+        // Now we have a row. Find the next number.
+        let mut sum_of_next = 0;
+        for row in rows {
+            let next = extrapolate(row, 1).expect("Extrapolated value overflows i128");
+            trace!("Next: {}", next);
+            sum_of_next += next;
+        }
+        sum_of_next
+    }
+
+    fn part2(rows: &Vec<Vec<i128>>) -> i128 {
+        // Find the previous number.
+        let mut sum_of_previous = 0;
+        for row in rows {
+            let previous = extrapolate(row, -1).expect("Extrapolated value overflows i128");
+            trace!("Previous: {}", previous);
+            sum_of_previous += previous;
+        }
+        sum_of_previous
+    }
+}
+
+pub fn parse_day09(input: &str) -> Result<Vec<Vec<i128>>, ParseError> {
+    // The input contains lines. Iterate over all lines and parse it as a list
+    // of numbers separated by spaces.
+    let mut rows = Vec::new();
+    for line in input_lines(9, input) {
+        let mut row = Vec::new();
+        for number in line.text.split(' ') {
+            row.push(line.parse::<i128>(number)?);
+        }
+        rows.push(row);
+    }
+    trace!("Rows: {:?}", rows);
+    Ok(rows)
+}
+
+pub fn find_next(row: &[i128]) -> i128 {
+    // First, calculate a vector of differences between each entry and the next.
+    // Track if all are zero.
+    let mut all_zero = true;
+    let mut differences = Vec::with_capacity(row.len() - 1);
+    for i in 0..(row.len() - 1) {
+        let difference = row[i + 1] - row[i];
+        if difference != 0 {
+            all_zero = false;
+        }
+        differences.push(difference);
+    }
+    if all_zero {
+        // All differences are zero. The next entry is simply the last entry.
+        row[row.len() - 1]
+    } else {
+        // Otherwise, recurse on the differences.
+        let next_difference = find_next(&differences);
+        // The next entry is the last entry plus the next difference.
+        row[row.len() - 1] + next_difference
+    }
+}
+
+pub fn find_previous(row: &[i128]) -> i128 {
+    // First, calculate a vector of differences between each entry and the next.
+    // Track if all are zero.
+    let mut all_zero = true;
+    let mut differences = Vec::with_capacity(row.len() - 1);
+    for i in 0..(row.len() - 1) {
+        let difference = row[i + 1] - row[i];
+        if difference != 0 {
+            all_zero = false;
+        }
+        differences.push(difference);
+    }
+    if all_zero {
+        // All differences are zero. The previous entry is simply the first entry.
+        row[0]
+    } else {
+        // Otherwise, recurse on the differences.
+        let previous_difference = find_previous(&differences);
+        // The previous entry is the first entry minus the previous difference.
+        row[0] - previous_difference
+    }
+}
+
+/// Predicts `k` steps after the last value of the row, or before the first one if `k` is negative.
+/// Returns None if the value doesn't fit into an `i128`.
+pub fn extrapolate(row: &[i128], k: i128) -> Option<i128> {
+    if k < 0 {
+        value_at(row, k)
+    } else {
+        value_at(row, (row.len() as i128 - 1).checked_add(k)?)
+    }
+}
+
+/// The value of the sequence at any `index`, where the row holds the values at 0, 1, ...
+///
+/// The sequence is the polynomial of the smallest degree through all values, which is
+/// what the differences method finds. Here we use the Newton form on the first entries
+/// of the difference rows: f(t) = sum over j of C(t, j) * d_j. Only the terms up to the
+/// last non-zero difference are added, so low degree rows stay small for any `t`.
+/// Returns None if the value or one of the terms doesn't fit into an `i128`.
+pub fn value_at(row: &[i128], index: i128) -> Option<i128> {
+    if let Some(value) = usize::try_from(index).ok().and_then(|i| row.get(i)) {
+        return Some(*value);
+    }
+
+    // The first entry of each difference row, starting with the row itself.
+    let mut differences = Vec::with_capacity(row.len());
+    let mut current = row.to_vec();
+    while let Some(&first) = current.first() {
+        differences.push(first);
+        current = current
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<_>>()?;
+    }
+    let degree = differences.iter().rposition(|d| *d != 0);
+    let Some(degree) = degree else {
+        return Some(0);
+    };
+
+    let t = index;
+    let mut binomial: i128 = 1; // C(t, j)
+    let mut value: i128 = 0;
+    for (j, difference) in differences[..=degree].iter().enumerate() {
+        value = value.checked_add(binomial.checked_mul(*difference)?)?;
+        if j == degree {
+            break;
+        }
+        // C(t, j+1) = C(t, j) * (t-j) / (j+1). Divide out the common factor first, so
+        // the product only overflows if the result does.
+        let j = j as i128;
+        let divisor = j + 1;
+        let common = gcd(binomial, divisor);
+        binomial = (binomial / common).checked_mul(t.checked_sub(j)? / (divisor / common))?;
+    }
+    Some(value)
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day09_test() {
+        let input = read_file("day09_test.txt").unwrap();
+        assert_eq!(Day09::solve_part1(&input), Ok(114));
+        assert_eq!(Day09::solve_part2(&input), Ok(2));
+    }
+
+    #[test]
+    fn extrapolates_anywhere() {
+        let row = [10, 13, 16, 21, 30, 45];
+        assert_eq!(extrapolate(&row, 1), Some(68));
+        assert_eq!(extrapolate(&row, -1), Some(5));
+        assert_eq!(extrapolate(&row, 0), Some(45));
+        assert_eq!(value_at(&row, 2), Some(16));
+
+        // f(t) = t^3 - 2t + 7, sampled at 0..5.
+        let f = |t: i128| t * t * t - 2 * t + 7;
+        let row: Vec<i128> = (0..5).map(f).collect();
+        for t in [-1000, -7, -1, 5, 6, 42, 1_000_000] {
+            assert_eq!(value_at(&row, t), Some(f(t)));
+        }
+        assert_eq!(extrapolate(&row, 3), Some(f(7)));
+        assert_eq!(extrapolate(&row, -3), Some(f(-3)));
+        assert_eq!(value_at(&row, 10_i128.pow(20)), None);
+    }
+
+    #[test]
+    fn extrapolates_low_degree_rows_far_away() {
+        // As long as the real input, where the Lagrange form used to overflow.
+        let constant = [5; 21];
+        let linear: Vec<i128> = (0..21).map(|t| 3 * t - 4).collect();
+        for t in [1000, -1000, 10_i128.pow(30)] {
+            assert_eq!(value_at(&constant, t), Some(5));
+            assert_eq!(value_at(&linear, t), Some(3 * t - 4));
+        }
+        assert_eq!(value_at(&constant, i128::MIN), Some(5));
+        assert_eq!(value_at(&linear, i128::MAX), None);
+    }
+
+    #[test]
+    fn matches_differences_method() {
+        let rows = parse_day09(&read_file("day09.txt").unwrap()).unwrap();
+        for row in &rows {
+            assert_eq!(extrapolate(row, 1), Some(find_next(row)));
+            assert_eq!(extrapolate(row, -1), Some(find_previous(row)));
+        }
+    }
+}
@@ -0,0 +1,571 @@
+use std::collections::{HashSet, VecDeque};
+
+use log::{debug, trace};
+
+use crate::{
+    error::{InputLine, ParseError},
+    geometry,
+    point::Direction,
+    solver::Solver,
+    utils::Grid,
+};
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = PipeMap;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<PipeMap, ParseError> {
+        parse_day10(input)
+    }
+
+    fn part1(map: &PipeMap) -> i32 {
+        let (loop_length, _) = solve_day10(map);
+        loop_length / 2
+    }
+
+    fn part2(map: &PipeMap) -> i32 {
+        let (_, enclosed) = solve_day10(map);
+        if log::log_enabled!(log::Level::Debug) {
+            let tiles = enclosed_by_scanline(map, &map.pipe_loop);
+            debug!("Loop:\n{}", render_loop(map, &map.pipe_loop, &tiles));
+        }
+        enclosed
+    }
+}
+
+#[derive(Debug)]
+pub struct PipeMap {
+    pub map: Grid<char>,
+    pub start_position: (i32, i32),
+    /// The pipe hidden under 'S', inferred from the pipes around it.
+    pub start_pipe: char,
+    /// All tiles on the loop in order, starting at 'S'.
+    pub pipe_loop: Vec<(i32, i32)>,
+}
+
+pub fn parse_day10(input: &str) -> Result<PipeMap, ParseError> {
+    let (map, start_position) = parse_pipe_grid(input)?;
+    let start_pipe = infer_start_pipe(&map, start_position).ok_or_else(|| {
+        line_at(input, start_position.1).error_at(
+            start_position.0 as usize,
+            "Start position must connect to exactly two pipes",
+        )
+    })?;
+    debug!("Start pipe: {}", start_pipe);
+
+    let mut pipe_map = PipeMap {
+        map,
+        start_position,
+        start_pipe,
+        pipe_loop: Vec::new(),
+    };
+    pipe_map.pipe_loop = find_loop(&pipe_map).map_err(|LoopBreak { position, message }| {
+        line_at(input, position.1).error_at(position.0 as usize, message)
+    })?;
+    Ok(pipe_map)
+}
+
+/// Reads the tiles and the position of the single 'S', without checking any pipes.
+pub fn parse_pipe_grid(input: &str) -> Result<(Grid<char>, (i32, i32)), ParseError> {
+    // Input looks like this
+    // ..F7.
+    // .FJ|.
+    // SJ.L7
+    // |F--J
+    // LJ...
+
+    // Read this into a grid and find the start position which holds the 'S' character.
+    let map = Grid::parse(10, input, |char| "|-LJ7F.S".contains(char).then_some(char))?;
+
+    let starts: Vec<(i32, i32)> = map
+        .iter()
+        .filter(|(_, char)| **char == 'S')
+        .map(|(position, _)| position)
+        .collect();
+    let Some(&start_position) = starts.first() else {
+        return Err(ParseError::new(
+            10,
+            map.height().saturating_sub(1),
+            0,
+            "",
+            "No start position 'S' found",
+        ));
+    };
+    if let Some(&(x, y)) = starts.get(1) {
+        return Err(line_at(input, y).error_at(x as usize, "Second start position"));
+    }
+    Ok((map, start_position))
+}
+
+fn line_at(input: &str, y: i32) -> InputLine<'_> {
+    InputLine {
+        day: 10,
+        index: y as usize,
+        text: input.lines().nth(y as usize).unwrap_or_default(),
+    }
+}
+
+/// Finds the pipe that connects 'S' to exactly two of its neighbours.
+fn infer_start_pipe(map: &Grid<char>, start_position: (i32, i32)) -> Option<char> {
+    use Direction::*;
+    let connected: Vec<Direction> = [Up, Down, Left, Right]
+        .into_iter()
+        .filter(|direction| {
+            let neighbour = map.get(start_position + *direction);
+            neighbour
+                .and_then(|pipe| pipe_openings(*pipe))
+                .is_some_and(|openings| openings.contains(&direction.reverse()))
+        })
+        .collect();
+    "|-LJ7F"
+        .chars()
+        .find(|pipe| pipe_openings(*pipe).is_some_and(|openings| openings == connected[..]))
+}
+
+/// The directions a pipe leads to, in the order Up, Down, Left, Right.
+pub fn pipe_openings(pipe: char) -> Option<[Direction; 2]> {
+    use Direction::*;
+    match pipe {
+        '|' => Some([Up, Down]),
+        '-' => Some([Left, Right]),
+        'L' => Some([Up, Right]),
+        'J' => Some([Up, Left]),
+        '7' => Some([Down, Left]),
+        'F' => Some([Down, Right]),
+        _ => None,
+    }
+}
+
+/// Returns the length of the loop together with the number of tiles it encloses.
+pub fn solve_day10(pipe_map: &PipeMap) -> (i32, i32) {
+    let pipe_loop = &pipe_map.pipe_loop;
+    // The centers of the loop tiles form a polygon. Its boundary points are the loop
+    // tiles, and the tiles enclosed by the loop are the lattice points inside.
+    let enclosed = geometry::interior_points(pipe_loop);
+    (pipe_loop.len() as i32, enclosed as i32)
+}
+
+/// Where and why the walk along the loop failed.
+struct LoopBreak {
+    position: (i32, i32),
+    message: &'static str,
+}
+
+/// Walks along the loop, starting at 'S', and returns all tiles on it in order.
+///
+/// If the loop is open, returns the tile where it breaks.
+fn find_loop(pipe_map: &PipeMap) -> Result<Vec<(i32, i32)>, LoopBreak> {
+    let start_position = pipe_map.start_position;
+    trace!("Map: {:?}", pipe_map.map);
+    debug!("Start position: {:?}", start_position);
+
+    let broken = |position, message, pipe_loop: &[(i32, i32)]| {
+        debug!(
+            "Loop is broken at {:?}:\n{}",
+            position,
+            render_loop(pipe_map, pipe_loop, &HashSet::new())
+        );
+        Err(LoopBreak { position, message })
+    };
+
+    // Walk along the pipe network, starting at 'S' until we reach 'S' again.
+    let Some([mut direction, _]) = pipe_openings(pipe_map.start_pipe) else {
+        return broken(start_position, "No pipe under the start position", &[]);
+    };
+    let mut pipe_loop = vec![start_position];
+    let mut current_position = start_position;
+    loop {
+        let next_position = current_position + direction;
+        if next_position == start_position {
+            break;
+        }
+        let Some(pipe) = pipe_map.pipe_at(next_position) else {
+            return broken(current_position, "Loop leads off the map", &pipe_loop);
+        };
+        trace!(
+            "Current position: {:?}, after {}",
+            next_position,
+            pipe_loop.len()
+        );
+        trace!("Current character: {:?}", pipe);
+        let Some(openings) =
+            pipe_openings(pipe).filter(|openings| openings.contains(&direction.reverse()))
+        else {
+            return broken(
+                next_position,
+                "Loop is broken, tile doesn't lead back",
+                &pipe_loop,
+            );
+        };
+        pipe_loop.push(next_position);
+        direction = if openings[0] == direction.reverse() {
+            openings[1]
+        } else {
+            openings[0]
+        };
+        current_position = next_position;
+    }
+    debug!("Distance walked: {}", pipe_loop.len());
+
+    Ok(pipe_loop)
+}
+
+impl PipeMap {
+    /// The pipe at `position`, with 'S' replaced by the pipe under it.
+    pub fn pipe_at(&self, position: (i32, i32)) -> Option<char> {
+        match self.map.get(position)? {
+            'S' => Some(self.start_pipe),
+            pipe => Some(*pipe),
+        }
+    }
+}
+
+/// Finds the enclosed tiles by scanning each row from the left and counting how often
+/// the loop is crossed. Tiles after an odd number of crossings are inside.
+///
+/// Only pipes that lead up count as a crossing. For "L-7" the loop goes from above to
+/// below, which crosses it once. For "L-J" it comes back up, which doesn't cross it.
+pub fn enclosed_by_scanline(pipe_map: &PipeMap, pipe_loop: &[(i32, i32)]) -> HashSet<(i32, i32)> {
+    let on_loop: HashSet<(i32, i32)> = pipe_loop.iter().copied().collect();
+    let mut enclosed = HashSet::new();
+    for y in 0..pipe_map.map.height() as i32 {
+        let mut inside = false;
+        for x in 0..pipe_map.map.width() as i32 {
+            if on_loop.contains(&(x, y)) {
+                if matches!(pipe_map.pipe_at((x, y)), Some('|' | 'L' | 'J')) {
+                    inside = !inside;
+                }
+            } else if inside {
+                enclosed.insert((x, y));
+            }
+        }
+    }
+    enclosed
+}
+
+/// Finds the enclosed tiles with a flood fill from the outside that can squeeze between pipes.
+///
+/// The map is drawn at double resolution with a border around it, so there is room
+/// between any two tiles. Tile (x, y) is at (2x + 1, 2y + 1) and the loop is a wall
+/// on its tiles and between tiles it connects.
+pub fn enclosed_by_flood_fill(pipe_map: &PipeMap, pipe_loop: &[(i32, i32)]) -> HashSet<(i32, i32)> {
+    let double = |(x, y): (i32, i32)| (2 * x + 1, 2 * y + 1);
+    let width = 2 * pipe_map.map.width() + 1;
+    let height = 2 * pipe_map.map.height() + 1;
+    let mut wall = Grid::new(width, height, false);
+    let next_tiles = pipe_loop.iter().cycle().skip(1);
+    for (tile, next_tile) in pipe_loop.iter().zip(next_tiles) {
+        let (x, y) = double(*tile);
+        let (next_x, next_y) = double(*next_tile);
+        *wall.get_mut((x, y)).unwrap() = true;
+        *wall.get_mut(((x + next_x) / 2, (y + next_y) / 2)).unwrap() = true;
+    }
+
+    let mut outside = Grid::new(width, height, false);
+    *outside.get_mut((0, 0)).unwrap() = true;
+    let mut todo = vec![(0, 0)];
+    while let Some(position) = todo.pop() {
+        for neighbour in outside.neighbours4(position).collect::<Vec<_>>() {
+            if wall.get(neighbour) == Some(&false) && outside.get(neighbour) == Some(&false) {
+                *outside.get_mut(neighbour).unwrap() = true;
+                todo.push(neighbour);
+            }
+        }
+    }
+
+    pipe_map
+        .map
+        .positions()
+        .filter(|tile| !wall.get(double(*tile)).unwrap() && !outside.get(double(*tile)).unwrap())
+        .collect()
+}
+
+/// Breadth first search from 'S' along connected pipes, with the number of steps to
+/// reach each tile. Tiles that can't be reached are None.
+///
+/// Two tiles are only connected if both pipes lead to each other. 'S' leads everywhere,
+/// so it is connected to every pipe that leads to it, even if that is more than two.
+/// This works on any map, whether or not 'S' is on a closed loop.
+pub fn distances_from_start(map: &Grid<char>, start_position: (i32, i32)) -> Grid<Option<u32>> {
+    let openings = |position| match map.get(position) {
+        Some('S') => Direction::ALL.to_vec(),
+        Some(pipe) => pipe_openings(*pipe).map_or(Vec::new(), Vec::from),
+        None => Vec::new(),
+    };
+    let mut distances = map.map(|_| None);
+    let Some(start_distance) = distances.get_mut(start_position) else {
+        return distances;
+    };
+    *start_distance = Some(0);
+    let mut todo = VecDeque::from([(start_position, 0)]);
+    while let Some((position, distance)) = todo.pop_front() {
+        for direction in openings(position) {
+            let neighbour = position + direction;
+            let connects_back = openings(neighbour).contains(&direction.reverse());
+            let Some(neighbour_distance) = distances.get_mut(neighbour) else {
+                continue;
+            };
+            if connects_back && neighbour_distance.is_none() {
+                *neighbour_distance = Some(distance + 1);
+                todo.push_back((neighbour, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// The largest distance and all tiles at that distance.
+pub fn farthest_tiles(distances: &Grid<Option<u32>>) -> (u32, Vec<(i32, i32)>) {
+    let max_distance = distances.iter().filter_map(|(_, d)| *d).max().unwrap_or(0);
+    let tiles = distances
+        .iter()
+        .filter(|(_, distance)| **distance == Some(max_distance))
+        .map(|(position, _)| position)
+        .collect();
+    (max_distance, tiles)
+}
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Draws the map with box-drawing characters, one line per row.
+///
+/// Pipes that are not part of `pipe_loop` are dimmed, ground is left empty and
+/// `enclosed` tiles are marked with a dot.
+pub fn render_loop(
+    pipe_map: &PipeMap,
+    pipe_loop: &[(i32, i32)],
+    enclosed: &HashSet<(i32, i32)>,
+) -> String {
+    let on_loop: HashSet<(i32, i32)> = pipe_loop.iter().copied().collect();
+    let mut result = String::new();
+    for y in 0..pipe_map.map.height() as i32 {
+        if y > 0 {
+            result.push('\n');
+        }
+        for x in 0..pipe_map.map.width() as i32 {
+            let pipe = pipe_map.pipe_at((x, y)).unwrap();
+            if on_loop.contains(&(x, y)) {
+                result.push(box_drawing(pipe));
+            } else if enclosed.contains(&(x, y)) {
+                result.push('•');
+            } else if pipe == '.' {
+                result.push(' ');
+            } else {
+                result.push_str(DIM);
+                result.push(box_drawing(pipe));
+                result.push_str(RESET);
+            }
+        }
+    }
+    result
+}
+
+fn box_drawing(pipe: char) -> char {
+    match pipe {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => ' ',
+    }
+}
+
+/// Draws the map as an SVG image with one unit per tile.
+///
+/// The loop is a closed black line, other pipes are light grey and enclosed tiles are filled.
+/// Returns the whole document, ready to be written to an `.svg` file.
+pub fn render_svg(
+    pipe_map: &PipeMap,
+    pipe_loop: &[(i32, i32)],
+    enclosed: &HashSet<(i32, i32)>,
+) -> String {
+    let (width, height) = (pipe_map.map.width(), pipe_map.map.height());
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">\n",
+        width, height
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+    let mut tiles: Vec<&(i32, i32)> = enclosed.iter().collect();
+    tiles.sort();
+    for (x, y) in tiles {
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"#8c8\"/>\n",
+            x, y
+        ));
+    }
+
+    let on_loop: HashSet<(i32, i32)> = pipe_loop.iter().copied().collect();
+    let mut junk = String::new();
+    for (position, _) in pipe_map.map.iter() {
+        let Some(openings) = pipe_map.pipe_at(position).and_then(pipe_openings) else {
+            continue;
+        };
+        if on_loop.contains(&position) {
+            continue;
+        }
+        let center = (position.0 as f64 + 0.5, position.1 as f64 + 0.5);
+        for direction in openings {
+            let step = direction.vector();
+            junk.push_str(&format!(
+                "M{} {}l{} {}",
+                center.0,
+                center.1,
+                step.x as f64 / 2.0,
+                step.y as f64 / 2.0
+            ));
+        }
+    }
+    if !junk.is_empty() {
+        svg.push_str(&format!(
+            "<path d=\"{}\" stroke=\"#ccc\" stroke-width=\"0.2\" fill=\"none\"/>\n",
+            junk
+        ));
+    }
+
+    let points: Vec<String> = pipe_loop
+        .iter()
+        .map(|(x, y)| format!("{}.5,{}.5", x, y))
+        .collect();
+    svg.push_str(&format!(
+        "<polygon points=\"{}\" stroke=\"black\" stroke-width=\"0.3\" fill=\"none\"/>\n",
+        points.join(" ")
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day10_test() {
+        let input = read_file("day10_test.txt").unwrap();
+        assert_eq!(Day10::solve_part1(&input), Ok(8));
+        assert_eq!(Day10::solve_part2(&input), Ok(1));
+    }
+
+    #[test]
+    fn finds_enclosed_tiles() {
+        // The example where the loop leaves a gap to squeeze through.
+        let input = "..........\n\
+                     .S------7.\n\
+                     .|F----7|.\n\
+                     .||....||.\n\
+                     .||....||.\n\
+                     .|L-7F-J|.\n\
+                     .|..||..|.\n\
+                     .L--JL--J.\n\
+                     ..........";
+        let map = parse_day10(input).unwrap();
+        let pipe_loop = &map.pipe_loop;
+        let enclosed = HashSet::from([(2, 6), (3, 6), (6, 6), (7, 6)]);
+        assert_eq!(enclosed_by_scanline(&map, pipe_loop), enclosed);
+        assert_eq!(enclosed_by_flood_fill(&map, pipe_loop), enclosed);
+        assert_eq!(solve_day10(&map).1, 4);
+
+        for file in ["day10_test.txt", "day10.txt"] {
+            let map = parse_day10(&read_file(file).unwrap()).unwrap();
+            let scanline = enclosed_by_scanline(&map, &map.pipe_loop);
+            assert_eq!(scanline.len() as i32, solve_day10(&map).1);
+            assert_eq!(enclosed_by_flood_fill(&map, &map.pipe_loop), scanline);
+        }
+    }
+
+    #[test]
+    fn renders_loop() {
+        let map = parse_day10("7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ").unwrap();
+        let pipe_loop = &map.pipe_loop;
+        let enclosed = HashSet::from([(0, 0)]);
+        let dim = |pipe| format!("{}{}{}", DIM, pipe, RESET);
+        let expected = [
+            format!("•{}┌┐{}", dim('─'), dim('─')),
+            format!(" ┌┘│{}", dim('┐')),
+            format!("┌┘{}└┐", dim('└')),
+            "│┌──┘".to_string(),
+            format!("└┘ {}{}", dim('└'), dim('┘')),
+        ];
+        assert_eq!(render_loop(&map, pipe_loop, &enclosed), expected.join("\n"));
+
+        let svg = render_svg(&map, pipe_loop, &enclosed);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 5 5\">"));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#8c8\"/>"));
+        assert!(svg.contains("<polygon points=\"0.5,2.5 0.5,3.5 0.5,4.5 "));
+        assert!(svg.contains("M1.5 0.5l-0.5 0M1.5 0.5l0.5 0"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn finds_distances_from_start() {
+        let map = parse_day10(&read_file("day10_test.txt").unwrap()).unwrap();
+        let distances = distances_from_start(&map.map, map.start_position);
+        assert_eq!(farthest_tiles(&distances), (8, vec![(4, 2)]));
+        let row: Vec<Option<u32>> = distances.row(2).to_vec();
+        assert_eq!(row, [Some(0), Some(1), None, Some(7), Some(8)]);
+
+        // The '-' in the top right leads to the loop, but the loop doesn't lead back.
+        let map = parse_day10("F-7-\n|.|.\nS-J.").unwrap();
+        let distances = distances_from_start(&map.map, map.start_position);
+        assert_eq!(distances.get((3, 0)), Some(&None));
+        assert_eq!(farthest_tiles(&distances), (4, vec![(2, 0)]));
+
+        let map = parse_day10(&read_file("day10.txt").unwrap()).unwrap();
+        let (farthest, _) = farthest_tiles(&distances_from_start(&map.map, map.start_position));
+        assert_eq!(farthest as i32, solve_day10(&map).0 / 2);
+    }
+
+    #[test]
+    fn finds_distances_from_start_with_branches() {
+        // 'S' connects to three pipes, which is no loop but still has distances.
+        let input = "....\n-S-7\n.|.|\n.L-J";
+        assert!(parse_day10(input).is_err());
+        let (map, start_position) = parse_pipe_grid(input).unwrap();
+        let distances = distances_from_start(&map, start_position);
+        let row: Vec<Option<u32>> = distances.row(1).to_vec();
+        assert_eq!(row, [Some(1), Some(0), Some(1), Some(2)]);
+        assert_eq!(distances.get((1, 2)), Some(&Some(1)));
+        assert_eq!(farthest_tiles(&distances), (4, vec![(3, 3)]));
+    }
+
+    #[test]
+    fn infers_start_pipe() {
+        let map = parse_day10("F-7\n|.|\nS-J").unwrap();
+        assert_eq!(map.start_pipe, 'L');
+        assert_eq!(solve_day10(&map).0, 8);
+
+        let map = parse_day10("FS7\nL-J").unwrap();
+        assert_eq!(map.start_pipe, '-');
+    }
+
+    #[test]
+    fn rejects_unconnected_start() {
+        let error = parse_day10("...\n.S-\n...").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(
+            error.message,
+            "Start position must connect to exactly two pipes"
+        );
+        assert!(parse_day10(".|.\n-S-\n.|.").is_err());
+    }
+
+    #[test]
+    fn rejects_open_loop() {
+        // The loop runs down the left edge off the map.
+        let error = parse_day10("LJ.\nS-7\n|.|").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.message, "Loop leads off the map");
+
+        // The '-' at the bottom leads to ground.
+        let error = parse_day10("S-7\n|.|\nL-.").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.message, "Loop is broken, tile doesn't lead back");
+    }
+}
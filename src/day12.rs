@@ -0,0 +1,617 @@
+use std::collections::HashMap;
+
+use log::{debug, trace};
+
+use crate::{
+    error::{input_lines, ParseError},
+    solver::Solver,
+};
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Vec<HotSpringRow>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Vec<HotSpringRow>, ParseError> {
+        parse_day12(input)
+    }
+
+    fn part1(rows: &Vec<HotSpringRow>) -> u128 {
+        solve_day12(rows).expect("Arrangement count overflows u128")
+    }
+
+    fn part2(rows: &Vec<HotSpringRow>) -> u128 {
+        solve_day12_unfolded(rows, 5).expect("Arrangement count overflows u128")
+    }
+}
+
+pub fn parse_day12(input: &str) -> Result<Vec<HotSpringRow>, ParseError> {
+    // First, parse the input. It looks like this:
+
+    // ???.### 1,1,3
+    // .??..??...?##. 1,1,3
+    // ?#?#?#?#?#?#?#? 1,3,1,6
+    // ????.#...#... 4,1,1
+    // ????.######..#####. 1,6,5
+    // ?###???????? 3,2,1
+
+    let mut rows = Vec::new();
+
+    // We parse this into Vec<HotSpringRow>:
+    for line in input_lines(12, input) {
+        // Split line along the single whitespace
+        let Some((state_str, contiguous_broken_str)) = line.text.split_once(' ') else {
+            return Err(line.error(line.text, "Expected springs and group sizes"));
+        };
+
+        let mut state = Vec::new();
+
+        for (i, char) in state_str.chars().enumerate() {
+            match char {
+                '.' => {
+                    state.push(HotSpringState::Operational);
+                }
+                '#' => {
+                    state.push(HotSpringState::Damaged);
+                }
+                '?' => {
+                    state.push(HotSpringState::Unknown);
+                }
+                _ => return Err(line.error_at(i, "Invalid character")),
+            }
+        }
+        // Now we have a state. Parse the contiguous broken numbers.
+        let contiguous_broken = contiguous_broken_str
+            .split(',')
+            .map(|s| line.parse::<u8>(s))
+            .collect::<Result<Vec<u8>, ParseError>>()?;
+
+        rows.push(HotSpringRow {
+            state,
+            contiguous_broken,
+        });
+    }
+    Ok(rows)
+}
+
+pub fn solve_day12(rows: &[HotSpringRow]) -> Option<u128> {
+    solve_day12_unfolded(rows, 1)
+}
+
+/// Sums the arrangements of all rows after unfolding each of them `factor` times.
+/// Returns None if the sum doesn't fit into a u128.
+pub fn solve_day12_unfolded(rows: &[HotSpringRow], factor: usize) -> Option<u128> {
+    let mut solution_count_sum: u128 = 0;
+    for row in rows {
+        let row = row.unfold(factor);
+        trace!("Row: {:?}", row);
+        let solution_count = count_arrangements(&row)?;
+        debug!("Solution count: {}", solution_count);
+        solution_count_sum = solution_count_sum.checked_add(solution_count)?;
+    }
+    Some(solution_count_sum)
+}
+
+/// Counts the ways to replace all unknowns so the row matches its contiguous broken groups.
+///
+/// This is a dynamic program over (position, completed groups), so it is polynomial in
+/// the row length instead of exponential in the number of unknowns.
+/// Returns None if the count doesn't fit into a u128.
+pub fn count_arrangements(row: &HotSpringRow) -> Option<u128> {
+    ArrangementCounter::new(row).count(0, 0, 0)
+}
+
+/// Arrangement counts for all states between two groups, computed from the end of the row.
+///
+/// A state in the middle of a group is resolved by placing the rest of the group, so only
+/// positions between groups are stored. For each group that is only the window of positions
+/// that leaves room for the groups before and after it, which keeps long unfolded rows small.
+/// Counts are None if they overflow.
+struct ArrangementCounter<'a> {
+    row: &'a HotSpringRow,
+    /// Number of operational cells before each index, to check where a run fits.
+    operational_before: Vec<usize>,
+    /// First position in the window of each group.
+    window_start: Vec<usize>,
+    /// `free[group][at - window_start[group]]` counts the arrangements of the cells from `at`
+    /// on, when `group` groups are complete and no run is in progress.
+    free: Vec<Vec<Option<u128>>>,
+}
+
+impl<'a> ArrangementCounter<'a> {
+    fn new(row: &'a HotSpringRow) -> Self {
+        let groups = &row.contiguous_broken;
+        let len = row.state.len();
+        // Cells needed for the groups from this index on, including one gap between groups.
+        let mut needed = vec![0; groups.len() + 1];
+        for group in (0..groups.len()).rev() {
+            let gap = if group + 1 < groups.len() { 1 } else { 0 };
+            needed[group] = groups[group] as usize + gap + needed[group + 1];
+        }
+        // Cells taken by the groups before this index, each followed by a gap.
+        let mut window_start = vec![0; groups.len() + 1];
+        for group in 0..groups.len() {
+            window_start[group + 1] = window_start[group] + groups[group] as usize + 1;
+        }
+        let mut operational_before = vec![0; len + 1];
+        for (at, cell) in row.state.iter().enumerate() {
+            operational_before[at + 1] =
+                operational_before[at] + (*cell == HotSpringState::Operational) as usize;
+        }
+
+        let mut counter = Self {
+            row,
+            operational_before,
+            window_start,
+            free: vec![Vec::new(); groups.len() + 1],
+        };
+        for group in (0..=groups.len()).rev() {
+            let start = counter.window_start[group];
+            let Some(end) = len.checked_sub(needed[group]).filter(|end| *end >= start) else {
+                continue;
+            };
+            counter.free[group] = vec![Some(0); end - start + 1];
+            for at in (start..=end).rev() {
+                let count = counter.count_free(at, group);
+                counter.free[group][at - start] = count;
+            }
+        }
+        counter
+    }
+
+    /// Arrangements of the cells from `at` on, when `group` groups are already complete
+    /// and the cells just before `at` are a run of `run` damaged springs.
+    /// None if the count overflows.
+    ///
+    /// Only states that can be reached from the start of the row are counted correctly.
+    fn count(&self, at: usize, group: usize, run: usize) -> Option<u128> {
+        if run == 0 {
+            self.free(at, group)
+        } else {
+            self.close(at, group, self.row.contiguous_broken[group] as usize - run)
+        }
+    }
+
+    /// The stored count between groups, zero outside of the window.
+    fn free(&self, at: usize, group: usize) -> Option<u128> {
+        let Some(index) = at.checked_sub(self.window_start[group]) else {
+            return Some(0);
+        };
+        self.free[group].get(index).copied().unwrap_or(Some(0))
+    }
+
+    /// Computes `free(at, group)` from the counts after it: cell `at` is either
+    /// operational, or the next group starts there.
+    fn count_free(&self, at: usize, group: usize) -> Option<u128> {
+        if at == self.row.state.len() {
+            return Some((group == self.row.contiguous_broken.len()) as u128);
+        }
+        let operational = match self.row.state[at] {
+            HotSpringState::Damaged => Some(0),
+            _ => self.free(at + 1, group),
+        };
+        let damaged = match self.row.contiguous_broken.get(group) {
+            Some(&size) => self.close(at, group, size as usize),
+            None => Some(0),
+        };
+        operational?.checked_add(damaged?)
+    }
+
+    /// Arrangements where the next `remaining` cells from `at` complete the current group,
+    /// followed by an operational cell or the end of the row.
+    fn close(&self, at: usize, group: usize, remaining: usize) -> Option<u128> {
+        let end = at + remaining;
+        let len = self.row.state.len();
+        if end > len || self.operational_before[end] != self.operational_before[at] {
+            return Some(0);
+        }
+        if end == len {
+            return Some((group + 1 == self.row.contiguous_broken.len()) as u128);
+        }
+        if self.row.state[end] == HotSpringState::Damaged {
+            return Some(0);
+        }
+        self.free(end + 1, group + 1)
+    }
+
+    /// The states cell `at` can take, damaged first.
+    fn choices(&self, at: usize) -> &'static [HotSpringState] {
+        match self.row.state[at] {
+            HotSpringState::Operational => &[HotSpringState::Operational],
+            HotSpringState::Damaged => &[HotSpringState::Damaged],
+            HotSpringState::Unknown => &[HotSpringState::Damaged, HotSpringState::Operational],
+        }
+    }
+
+    /// The (group, run) after the next cell is `cell`, or None if that breaks the groups.
+    fn step(&self, group: usize, run: usize, cell: HotSpringState) -> Option<(usize, usize)> {
+        let groups = &self.row.contiguous_broken;
+        match cell {
+            // Damaged: extend the current run.
+            HotSpringState::Damaged => {
+                (group < groups.len() && run < groups[group] as usize).then_some((group, run + 1))
+            }
+            // Operational: close the current run, if it is complete.
+            HotSpringState::Operational => {
+                if run == 0 {
+                    Some((group, 0))
+                } else if run == groups[group] as usize {
+                    Some((group + 1, 0))
+                } else {
+                    None
+                }
+            }
+            HotSpringState::Unknown => unreachable!("Only known states are placed"),
+        }
+    }
+
+    /// The `k`-th arrangement in the order of `HotSpringRow::arrangements`.
+    ///
+    /// Counts that overflow are larger than any `k`.
+    fn nth(&self, mut k: u128) -> Option<Vec<HotSpringState>> {
+        if self.count(0, 0, 0).is_some_and(|total| k >= total) {
+            return None;
+        }
+        let mut arrangement = Vec::with_capacity(self.row.state.len());
+        let (mut group, mut run) = (0, 0);
+        for at in 0..self.row.state.len() {
+            for &cell in self.choices(at) {
+                let Some((next_group, next_run)) = self.step(group, run, cell) else {
+                    continue;
+                };
+                match self.count(at + 1, next_group, next_run) {
+                    Some(count) if k >= count => k -= count,
+                    _ => {
+                        arrangement.push(cell);
+                        (group, run) = (next_group, next_run);
+                        break;
+                    }
+                }
+            }
+        }
+        Some(arrangement)
+    }
+
+    /// For each cell, how many arrangements have it damaged and how many operational.
+    ///
+    /// A count is None if it overflows. It is still known to be non-zero then.
+    fn cell_counts(&self) -> Vec<(Option<u128>, Option<u128>)> {
+        // a + b * c, where None stands for a non-zero value that overflowed.
+        fn add_product(a: Option<u128>, b: Option<u128>, c: Option<u128>) -> Option<u128> {
+            a?.checked_add(b?.checked_mul(c?)?)
+        }
+
+        let mut cell_counts = Vec::with_capacity(self.row.state.len());
+        // Number of ways to reach each (group, run) before the current cell.
+        // These are never zero, so a None can only be a large count.
+        let mut prefixes: HashMap<(usize, usize), Option<u128>> =
+            HashMap::from([((0, 0), Some(1))]);
+        for at in 0..self.row.state.len() {
+            let mut damaged = Some(0);
+            let mut operational = Some(0);
+            let mut next_prefixes = HashMap::new();
+            for ((group, run), prefix) in prefixes {
+                for &cell in self.choices(at) {
+                    let Some(next) = self.step(group, run, cell) else {
+                        continue;
+                    };
+                    let suffix = self.count(at + 1, next.0, next.1);
+                    if suffix == Some(0) {
+                        continue;
+                    }
+                    match cell {
+                        HotSpringState::Damaged => damaged = add_product(damaged, prefix, suffix),
+                        _ => operational = add_product(operational, prefix, suffix),
+                    }
+                    let next_prefix = next_prefixes.entry(next).or_insert(Some(0));
+                    *next_prefix = add_product(*next_prefix, prefix, Some(1));
+                }
+            }
+            cell_counts.push((damaged, operational));
+            prefixes = next_prefixes;
+        }
+        cell_counts
+    }
+}
+
+/// Lazy iterator over all arrangements of a row, see `HotSpringRow::arrangements`.
+pub struct Arrangements<'a> {
+    counter: ArrangementCounter<'a>,
+    next: u128,
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<HotSpringState>;
+
+    fn next(&mut self) -> Option<Vec<HotSpringState>> {
+        let arrangement = self.counter.nth(self.next)?;
+        self.next += 1;
+        Some(arrangement)
+    }
+}
+
+/// Renders springs as in the puzzle input, e.g. "#.#?".
+pub fn render_state(state: &[HotSpringState]) -> String {
+    state
+        .iter()
+        .map(|cell| match cell {
+            HotSpringState::Operational => '.',
+            HotSpringState::Damaged => '#',
+            HotSpringState::Unknown => '?',
+        })
+        .collect()
+}
+
+/// Brute force reference for `count_arrangements`, exponential in the number of unknowns.
+pub fn count_solutions(row: &HotSpringRow, at: usize) -> usize {
+    // Use backtracking to count the number of solutions.
+    // Check if in the current position, we are somewhat consistent with the contiguous broken numbers.
+    if at == row.state.len() {
+        // We are at the end of the row. Verify the solution.
+        if verify_solution(row) {
+            1
+        } else {
+            0
+        }
+    } else if row.state[at] != HotSpringState::Unknown {
+        if at + 1 < row.state.len() {
+            count_solutions(row, at + 1)
+        } else {
+            // Verify solution
+            if verify_solution(row) {
+                1
+            } else {
+                0
+            }
+        }
+    } else {
+        let mut clone = row.clone();
+        clone.state[at] = HotSpringState::Operational;
+        let mut count = count_solutions(&clone, at + 1);
+        clone.state[at] = HotSpringState::Damaged;
+        count += count_solutions(&clone, at + 1);
+        count
+    }
+}
+
+/// Takes a HotSpringRow without any unknowns and verifies that it is a valid solution.
+pub fn verify_solution(row: &HotSpringRow) -> bool {
+    let mut runs = Vec::new();
+    let mut current_run = 0;
+    for state in &row.state {
+        match state {
+            HotSpringState::Damaged => {
+                current_run += 1;
+            }
+            HotSpringState::Operational => {
+                if current_run > 0 {
+                    runs.push(current_run);
+                    current_run = 0;
+                }
+            }
+            HotSpringState::Unknown => panic!("Unknown state in verify_solution"),
+        }
+    }
+
+    if current_run > 0 {
+        runs.push(current_run);
+    }
+
+    runs == row.contiguous_broken
+}
+
+#[derive(Debug, Clone)]
+pub struct HotSpringRow {
+    pub state: Vec<HotSpringState>,
+    pub contiguous_broken: Vec<u8>,
+}
+
+impl HotSpringRow {
+    /// Repeats the row `factor` times, with an unknown spring between the copies.
+    /// The contiguous broken groups are repeated as well.
+    ///
+    /// Counts grow quickly with the factor, see `count_arrangements` for overflows.
+    pub fn unfold(&self, factor: usize) -> HotSpringRow {
+        let mut state = Vec::with_capacity((self.state.len() + 1) * factor);
+        for i in 0..factor {
+            if i > 0 {
+                state.push(HotSpringState::Unknown);
+            }
+            state.extend_from_slice(&self.state);
+        }
+        HotSpringRow {
+            state,
+            contiguous_broken: self.contiguous_broken.repeat(factor),
+        }
+    }
+
+    /// Iterates over all valid arrangements without unknowns, in lexicographic order
+    /// of their rendering, so "#" comes before ".".
+    pub fn arrangements(&self) -> Arrangements<'_> {
+        Arrangements {
+            counter: ArrangementCounter::new(self),
+            next: 0,
+        }
+    }
+
+    /// The `k`-th arrangement (zero based) in the order of `arrangements`, found
+    /// without enumerating the ones before it.
+    pub fn nth_arrangement(&self, k: u128) -> Option<Vec<HotSpringState>> {
+        ArrangementCounter::new(self).nth(k)
+    }
+
+    /// For each cell, the state it has in every valid arrangement, or None if it differs
+    /// between arrangements. Returns None if there is no valid arrangement at all.
+    pub fn forced_cells(&self) -> Option<Vec<Option<HotSpringState>>> {
+        let counter = ArrangementCounter::new(self);
+        if counter.count(0, 0, 0) == Some(0) {
+            return None;
+        }
+        let forced = counter
+            .cell_counts()
+            .into_iter()
+            .map(|counts| match counts {
+                (_, Some(0)) => Some(HotSpringState::Damaged),
+                (Some(0), _) => Some(HotSpringState::Operational),
+                _ => None,
+            })
+            .collect();
+        Some(forced)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotSpringState {
+    Operational, // '.'
+    Damaged,     // '#'
+    Unknown,     // '?'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day12_test() {
+        let input = read_file("day12_test.txt").unwrap();
+        assert_eq!(Day12::solve_part1(&input), Ok(21));
+        assert_eq!(Day12::solve_part2(&input), Ok(525152));
+    }
+
+    #[test]
+    fn unfolds_rows() {
+        let row = &parse_day12(".# 1").unwrap()[0];
+        let unfolded = &parse_day12(".#?.#?.# 1,1,1").unwrap()[0];
+        assert_eq!(row.unfold(3).state, unfolded.state);
+        assert_eq!(row.unfold(3).contiguous_broken, unfolded.contiguous_broken);
+    }
+
+    #[test]
+    fn counts_large_unfold_factors() {
+        let rows = parse_day12("???.### 1,1,3").unwrap();
+        // Only the first copy of "???" has a choice once the groups are fixed.
+        assert_eq!(solve_day12_unfolded(&rows, 30), Some(1));
+        let rows = parse_day12("???.### 1,1,3\n#? 1").unwrap();
+        assert_eq!(solve_day12_unfolded(&rows, 1000), Some(2));
+        let rows = parse_day12(".??..??...?##. 1,1,3").unwrap();
+        assert_eq!(solve_day12_unfolded(&rows, 5), Some(16384));
+        // Every further copy multiplies the count by 8.
+        assert_eq!(solve_day12_unfolded(&rows, 20), Some(4 * 8_u128.pow(19)));
+    }
+
+    #[test]
+    fn counts_long_rows() {
+        // 80000 cells, too many for a recursive search or a table over all positions.
+        let row = &parse_day12("???.### 1,1,3").unwrap()[0];
+        assert_eq!(count_arrangements(&row.unfold(10_000)), Some(1));
+        let row = &parse_day12("#?? 1").unwrap()[0];
+        assert_eq!(count_arrangements(&row.unfold(300)), Some(1));
+        // 40 single springs with gaps between them in 119 cells, C(80, 40) ways.
+        let row = &parse_day12("?? 1").unwrap()[0];
+        assert_eq!(
+            count_arrangements(&row.unfold(40)),
+            Some(107507208733336176461620)
+        );
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let rows = parse_day12("???????????????? 1,1").unwrap();
+        assert_eq!(
+            solve_day12_unfolded(&rows, 15),
+            Some(18317746084264169967729625407698632560)
+        );
+        // Factor 16 needs 133 bits, factors 20 and 30 used to wrap around silently.
+        for factor in [16, 20, 30] {
+            assert_eq!(solve_day12_unfolded(&rows, factor), None);
+        }
+
+        // Each row fits, but their sum doesn't.
+        let huge = rows[0].unfold(15);
+        let count = count_arrangements(&huge).unwrap();
+        let copies = (u128::MAX / count) as usize;
+        assert!(solve_day12_unfolded(&vec![huge.clone(); copies], 1).is_some());
+        assert_eq!(solve_day12_unfolded(&vec![huge; copies + 1], 1), None);
+    }
+
+    #[test]
+    fn arrangements_match_brute_force() {
+        let input = read_file("day12_test.txt").unwrap();
+        for row in parse_day12(&input).unwrap() {
+            assert_eq!(
+                count_arrangements(&row),
+                Some(count_solutions(&row, 0) as u128),
+                "{:?}",
+                row
+            );
+        }
+    }
+
+    #[test]
+    fn enumerates_arrangements_in_order() {
+        let row = &parse_day12("?#?#?#?#?#?#?#? 1,3,1,6").unwrap()[0];
+        let all: Vec<String> = row.arrangements().map(|a| render_state(&a)).collect();
+        assert_eq!(all, vec![".#.###.#.######"]);
+
+        let row = &parse_day12("?###???????? 3,2,1").unwrap()[0];
+        let all: Vec<String> = row.arrangements().map(|a| render_state(&a)).collect();
+        assert_eq!(all.len(), 10);
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(all[0], ".###.##.#...");
+        for (k, arrangement) in all.iter().enumerate() {
+            assert_eq!(
+                render_state(&row.nth_arrangement(k as u128).unwrap()),
+                *arrangement
+            );
+        }
+        assert_eq!(row.nth_arrangement(10), None);
+    }
+
+    #[test]
+    fn samples_arrangement_of_large_row() {
+        let row = &parse_day12(&format!("{} 1,1", "?".repeat(60))).unwrap()[0];
+        let last = row.nth_arrangement(59 * 58 / 2 - 1).unwrap();
+        assert_eq!(render_state(&last), format!("{}#.#", ".".repeat(57)));
+    }
+
+    #[test]
+    fn finds_forced_cells() {
+        let row = &parse_day12("?###???????? 3,2,1").unwrap()[0];
+        let forced: String = row
+            .forced_cells()
+            .unwrap()
+            .iter()
+            .map(|cell| render_state(&[cell.unwrap_or(HotSpringState::Unknown)]))
+            .collect();
+        assert_eq!(forced, ".###.???????");
+
+        let impossible = &parse_day12("#.# 3").unwrap()[0];
+        assert_eq!(impossible.forced_cells(), None);
+
+        // Far too many arrangements to count, but the "." and "#" are still forced.
+        let unknowns = "?".repeat(16);
+        let row = &parse_day12(&format!("{}.#.{} 1,1,1", unknowns, unknowns)).unwrap()[0];
+        let row = row.unfold(20);
+        assert_eq!(count_arrangements(&row), None);
+        let forced = row.forced_cells().unwrap();
+        assert_eq!(
+            forced[16..19],
+            [
+                Some(HotSpringState::Operational),
+                Some(HotSpringState::Damaged),
+                Some(HotSpringState::Operational)
+            ]
+        );
+        assert_eq!(forced[0], None);
+    }
+
+    #[test]
+    fn counts_many_unknowns_quickly() {
+        // 60 unknowns would take forever to enumerate.
+        let row = &parse_day12(&format!("{} 1,1", "?".repeat(60))).unwrap()[0];
+        assert_eq!(count_arrangements(row), Some(59 * 58 / 2));
+    }
+}
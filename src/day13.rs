@@ -0,0 +1,402 @@
+use std::{cmp::Ordering, fmt::Debug};
+
+use log::{debug, trace};
+
+use crate::{
+    error::{input_lines, InputLine, ParseError},
+    solver::Solver,
+    utils::Grid,
+};
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input = Vec<Day13Block<u64>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Day13Block<u64>>, ParseError> {
+        parse_day13(input)
+    }
+
+    fn part1(blocks: &Vec<Day13Block<u64>>) -> usize {
+        mirror_value(blocks)
+    }
+
+    fn part2(blocks: &Vec<Day13Block<u64>>) -> usize {
+        smudge_value(blocks)
+    }
+}
+
+/// Parses the blocks into bitboards of type `R`. Blocks that don't fit into `R` are an error.
+pub fn parse_day13<R: BitRow>(input: &str) -> Result<Vec<Day13Block<R>>, ParseError> {
+    // For Day 13, the input consists of blocks, separated by empty lines.
+
+    // #.##..##.
+    // ..#.##.#.
+    // ##......#
+    // ##......#
+    // ..#.##.#.
+    // ..##..##.
+    // #.#.##.#.
+    //
+    // #...##..#
+    // #....#..#
+    // ..##..###
+    // #####.##.
+    // #####.##.
+    // ..##..###
+    // #....#..#
+
+    // Each block should be parsed into two vectors of bit-boards. One for rows and one for columns.
+
+    let mut blocks = Vec::new();
+    let mut block_lines = Vec::new();
+    for line in input_lines(13, input) {
+        if line.text.is_empty() {
+            blocks.push(parse_block(&block_lines)?);
+            block_lines.clear();
+        } else {
+            block_lines.push(line);
+        }
+    }
+    if !block_lines.is_empty() {
+        blocks.push(parse_block(&block_lines)?);
+    }
+    Ok(blocks)
+}
+
+fn parse_block<R: BitRow>(lines: &[InputLine]) -> Result<Day13Block<R>, ParseError> {
+    if let Some(capacity) = R::CAPACITY {
+        if let Some(line) = lines.iter().find(|line| line.text.len() > capacity) {
+            let message = format!("Pattern is wider than {} cells", capacity);
+            return Err(line.error_at(capacity, message));
+        }
+        if let Some(line) = lines.get(capacity) {
+            let message = format!("Pattern is taller than {} cells", capacity);
+            return Err(line.error(line.text, message));
+        }
+    }
+    let grid = Grid::parse_lines(lines.iter().copied(), |char| match char {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(Day13Block {
+        rows: grid.rows().map(|row| bit_row(row, grid.width())).collect(),
+        columns: grid
+            .columns()
+            .map(|column| bit_row(column, grid.height()))
+            .collect(),
+    })
+}
+
+fn bit_row<'a, R: BitRow>(cells: impl IntoIterator<Item = &'a bool>, len: usize) -> R {
+    let mut row = R::empty(len);
+    for (i, rock) in cells.into_iter().enumerate() {
+        if *rock {
+            row.set(i);
+        }
+    }
+    row
+}
+
+pub fn mirror_value<R: BitRow>(blocks: &[Day13Block<R>]) -> usize {
+    mirror_value_with_errors(blocks, 0)
+}
+
+/// Each block has a single smudge. Fixing it gives a new reflection line, which is
+/// a line where the two halves differ in exactly one cell.
+pub fn smudge_value<R: BitRow>(blocks: &[Day13Block<R>]) -> usize {
+    mirror_value_with_errors(blocks, 1)
+}
+
+/// Sums up the reflection lines where the two halves of a block differ in exactly `errors` cells.
+pub fn mirror_value_with_errors<R: BitRow>(blocks: &[Day13Block<R>], errors: u32) -> usize {
+    let mut total_value = 0;
+    for block in blocks {
+        let vertical_mirror = find_mirror_with_errors(&block.columns, errors);
+        let horizontal_mirror = find_mirror_with_errors(&block.rows, errors);
+        debug!(
+            "Vertical mirror: {:?}, horizontal mirror: {:?}",
+            vertical_mirror, horizontal_mirror
+        );
+        if vertical_mirror.is_none() && horizontal_mirror.is_none() {
+            trace!("No mirror with {} errors in block: {:?}", errors, block);
+        } else if log::log_enabled!(log::Level::Debug) {
+            let reflections = block.reflections(errors);
+            if reflections.len() > 1 {
+                debug!(
+                    "Ambiguous block, only the first of each is used: {:?}",
+                    reflections
+                );
+            }
+        }
+        if let Some(vertical_mirror) = vertical_mirror {
+            total_value += vertical_mirror + 1
+        }
+        if let Some(horizontal_mirror) = horizontal_mirror {
+            total_value += 100 * (horizontal_mirror + 1)
+        }
+    }
+    total_value
+}
+
+#[derive(Debug, Clone)]
+pub struct Day13Block<R> {
+    pub rows: Vec<R>,
+    pub columns: Vec<R>,
+}
+
+/// One row or column of a pattern as a bitboard, with bit `i` set for a rock at index `i`.
+pub trait BitRow: Clone + Eq + Debug {
+    /// The most cells a row can hold, or None if there is no limit.
+    const CAPACITY: Option<usize>;
+
+    /// A row of `len` cells without rocks. Rows may grow beyond `len` up to the capacity.
+    fn empty(len: usize) -> Self;
+    fn set(&mut self, i: usize);
+    /// Number of cells that differ between the two rows.
+    fn differences(&self, other: &Self) -> u32;
+}
+
+macro_rules! impl_bit_row {
+    ($($int:ty),*) => {$(
+        impl BitRow for $int {
+            const CAPACITY: Option<usize> = Some(<$int>::BITS as usize);
+
+            fn empty(_len: usize) -> Self {
+                0
+            }
+
+            fn set(&mut self, i: usize) {
+                *self |= 1 << i;
+            }
+
+            fn differences(&self, other: &Self) -> u32 {
+                (self ^ other).count_ones()
+            }
+        }
+    )*};
+}
+
+impl_bit_row!(u32, u64, u128);
+
+/// A bitboard of any size, for patterns that don't fit into an integer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitVec(Vec<u64>);
+
+impl BitVec {
+    fn word_mut(&mut self, i: usize) -> &mut u64 {
+        let word = i / 64;
+        if word >= self.0.len() {
+            self.0.resize(word + 1, 0);
+        }
+        &mut self.0[word]
+    }
+}
+
+impl BitRow for BitVec {
+    const CAPACITY: Option<usize> = None;
+
+    fn empty(len: usize) -> Self {
+        BitVec(vec![0; len.div_ceil(64)])
+    }
+
+    fn set(&mut self, i: usize) {
+        *self.word_mut(i) |= 1 << (i % 64);
+    }
+
+    fn differences(&self, other: &Self) -> u32 {
+        let (short, long) = if self.0.len() <= other.0.len() {
+            (&self.0, &other.0)
+        } else {
+            (&other.0, &self.0)
+        };
+        let common: u32 = short
+            .iter()
+            .zip(long)
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum();
+        let rest: u32 = long[short.len()..]
+            .iter()
+            .map(|word| word.count_ones())
+            .sum();
+        common + rest
+    }
+}
+
+/// Finds the first reflection line where the two halves differ in exactly `errors` cells.
+///
+/// Returns the index of the last entry before the line, so the line lies between
+/// `index` and `index + 1`.
+pub fn find_mirror_with_errors<R: BitRow>(axis: &[R], errors: u32) -> Option<usize> {
+    (0..axis.len().saturating_sub(1)).find(|&index| has_errors(axis, index, errors))
+}
+
+/// Whether the halves before and after `index` differ in exactly `errors` cells.
+fn has_errors<R: BitRow>(axis: &[R], index: usize, errors: u32) -> bool {
+    let before = axis[..=index].iter().rev();
+    let after = axis[index + 1..].iter();
+    let mut differences = 0;
+    for (a, b) in before.zip(after) {
+        differences += a.differences(b);
+        if differences > errors {
+            return false;
+        }
+    }
+    differences == errors
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// A line between two columns.
+    Vertical,
+    /// A line between two rows.
+    Horizontal,
+}
+
+/// The side of a reflection line that runs out of rows or columns first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Border {
+    /// The top or left side.
+    Start,
+    /// The bottom or right side.
+    End,
+    /// The line is in the middle, both sides are used up.
+    Both,
+}
+
+/// A reflection line of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reflection {
+    pub orientation: Orientation,
+    /// Index of the last row or column before the line.
+    pub index: usize,
+    /// How many rows or columns on each side are mirrored.
+    pub reach: usize,
+    pub border: Border,
+}
+
+impl Reflection {
+    /// The value of the line in the puzzle summary.
+    pub fn value(&self) -> usize {
+        match self.orientation {
+            Orientation::Vertical => self.index + 1,
+            Orientation::Horizontal => 100 * (self.index + 1),
+        }
+    }
+}
+
+impl<R: BitRow> Day13Block<R> {
+    /// All reflection lines where the two halves differ in exactly `errors` cells.
+    /// Vertical lines come first, each orientation in order of the index.
+    pub fn reflections(&self, errors: u32) -> Vec<Reflection> {
+        let axes = [
+            (Orientation::Vertical, &self.columns),
+            (Orientation::Horizontal, &self.rows),
+        ];
+        let mut reflections = Vec::new();
+        for (orientation, axis) in axes {
+            for index in 0..axis.len().saturating_sub(1) {
+                if !has_errors(axis, index, errors) {
+                    continue;
+                }
+                let before = index + 1;
+                let after = axis.len() - before;
+                reflections.push(Reflection {
+                    orientation,
+                    index,
+                    reach: before.min(after),
+                    border: match before.cmp(&after) {
+                        Ordering::Less => Border::Start,
+                        Ordering::Greater => Border::End,
+                        Ordering::Equal => Border::Both,
+                    },
+                });
+            }
+        }
+        reflections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_file;
+
+    #[test]
+    fn day13_test() {
+        let input = read_file("day13_test.txt").unwrap();
+        assert_eq!(Day13::solve_part1(&input), Ok(405));
+        assert_eq!(Day13::solve_part2(&input), Ok(400));
+    }
+
+    #[test]
+    fn finds_mirror_with_errors() {
+        let axis: [u32; 6] = [0b001, 0b010, 0b100, 0b100, 0b010, 0b011];
+        assert_eq!(find_mirror_with_errors(&axis, 0), None);
+        assert_eq!(find_mirror_with_errors(&axis, 1), Some(2));
+        assert_eq!(find_mirror_with_errors(&axis, 2), Some(0));
+    }
+
+    #[test]
+    fn reports_all_reflections() {
+        let blocks = parse_day13::<u32>(&read_file("day13_test.txt").unwrap()).unwrap();
+        assert_eq!(
+            blocks[0].reflections(0),
+            vec![Reflection {
+                orientation: Orientation::Vertical,
+                index: 4,
+                reach: 4,
+                border: Border::End,
+            }]
+        );
+        assert_eq!(
+            blocks[1].reflections(1),
+            vec![Reflection {
+                orientation: Orientation::Horizontal,
+                index: 0,
+                reach: 1,
+                border: Border::Start,
+            }]
+        );
+
+        // Three equal rows have a reflection after each of the first two.
+        let block = parse_day13::<u32>("#.\n#.\n#.").unwrap().remove(0);
+        let reflections = block.reflections(0);
+        let summary: Vec<_> = reflections
+            .iter()
+            .map(|r| (r.orientation, r.index, r.reach, r.border, r.value()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Orientation::Horizontal, 0, 1, Border::Start, 100),
+                (Orientation::Horizontal, 1, 1, Border::End, 200),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_wide_patterns() {
+        // Each row of the example followed by its mirror image, four times over.
+        // This adds a vertical reflection after the 9th column of both blocks.
+        let input = read_file("day13_test.txt").unwrap();
+        let wide: String = input
+            .lines()
+            .map(|line| {
+                let mirrored: String = line.chars().rev().collect();
+                format!("{}\n", format!("{}{}", line, mirrored).repeat(4))
+            })
+            .collect();
+
+        let blocks = parse_day13::<BitVec>(&wide).unwrap();
+        assert_eq!(mirror_value(&blocks), 9 + 9 + 400);
+        assert_eq!(mirror_value(&parse_day13::<u128>(&wide).unwrap()), 418);
+
+        let error = parse_day13::<u64>(&wide).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 65));
+        assert_eq!(error.message, "Pattern is wider than 64 cells");
+    }
+}
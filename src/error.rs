@@ -0,0 +1,117 @@
+//! Error type shared by all input parsers.
+
+use std::{fmt::Display, str::FromStr};
+
+/// Malformed puzzle input, with the position of the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// One based line number.
+    pub line: usize,
+    /// One based column, counted in characters.
+    pub column: usize,
+    /// The text that could not be parsed.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error from zero based indices, as returned by `enumerate`.
+    pub fn new(
+        day: u8,
+        line_index: usize,
+        column_index: usize,
+        text: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            day,
+            line: line_index + 1,
+            column: column_index + 1,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {:02}, line {}, column {}: {}: {:?}",
+            self.day, self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A single line of puzzle input, which knows where it is to report errors.
+#[derive(Debug, Clone, Copy)]
+pub struct InputLine<'a> {
+    pub day: u8,
+    pub index: usize,
+    pub text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+    /// Creates an error for `part`, which should be a slice of this line.
+    pub fn error(&self, part: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, self.index, self.column_of(part), part, message)
+    }
+
+    /// Creates an error for the character at the zero based `column_index`.
+    pub fn error_at(&self, column_index: usize, message: impl Into<String>) -> ParseError {
+        let text = self
+            .text
+            .chars()
+            .nth(column_index)
+            .map(String::from)
+            .unwrap_or_default();
+        ParseError::new(self.day, self.index, column_index, text, message)
+    }
+
+    /// Parses `part`, which should be a slice of this line, into a number or similar.
+    pub fn parse<T: FromStr>(&self, part: &str) -> Result<T, ParseError> {
+        part.trim()
+            .parse()
+            .map_err(|_| self.error(part, format!("Expected {}", std::any::type_name::<T>())))
+    }
+
+    /// Character column of `part` inside this line, or 0 if it is not a slice of it.
+    fn column_of(&self, part: &str) -> usize {
+        (part.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .and_then(|offset| self.text.get(..offset))
+            .map_or(0, |prefix| prefix.chars().count())
+    }
+}
+
+/// Iterates over the lines of an input, tagged with their position.
+pub fn input_lines(day: u8, input: &str) -> impl Iterator<Item = InputLine<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(index, text)| InputLine { day, index, text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_position_of_slice() {
+        let line = InputLine {
+            day: 9,
+            index: 2,
+            text: "1 2 x 4",
+        };
+        let error = line.parse::<i32>(&line.text[4..5]).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 5));
+        assert_eq!(error.text, "x");
+        assert_eq!(
+            error.to_string(),
+            "Day 09, line 3, column 5: Expected i32: \"x\""
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Area and lattice point counts of polygons with integer vertices.
+//!
+//! Vertices are given in order around the polygon, in either direction. The last vertex
+//! connects back to the first one. Coordinates can be any integer type that fits into
+//! an `i128`, which is also what all results are computed in.
+
+/// Twice the area of the polygon, with the shoelace formula.
+///
+/// Doubling keeps the result exact, the area of a lattice polygon can end in one half.
+pub fn double_area<T: Copy + Into<i128>>(vertices: &[(T, T)]) -> i128 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum::<i128>()
+        .abs()
+}
+
+/// The length of the border of the polygon.
+pub fn perimeter<T: Copy + Into<i128>>(vertices: &[(T, T)]) -> f64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| ((x2 - x1) as f64).hypot((y2 - y1) as f64))
+        .sum()
+}
+
+/// The number of lattice points on the border of the polygon.
+///
+/// For polygons with only horizontal and vertical edges this is the perimeter.
+pub fn boundary_points<T: Copy + Into<i128>>(vertices: &[(T, T)]) -> i128 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| gcd((x2 - x1).abs(), (y2 - y1).abs()))
+        .sum()
+}
+
+/// The number of lattice points strictly inside the polygon, by Pick's theorem:
+/// A = I + B/2 - 1.
+pub fn interior_points<T: Copy + Into<i128>>(vertices: &[(T, T)]) -> i128 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// All edges as pairs of vertices, including the one closing the polygon.
+fn edges<T: Copy + Into<i128>>(
+    vertices: &[(T, T)],
+) -> impl Iterator<Item = ((i128, i128), (i128, i128))> + '_ {
+    let widen = |(x, y): (T, T)| (x.into(), y.into());
+    let next = vertices.iter().cycle().skip(1);
+    vertices
+        .iter()
+        .zip(next)
+        .map(move |(a, b)| (widen(*a), widen(*b)))
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_rectangle() {
+        let rectangle: [(i64, i64); 4] = [(0, 0), (4, 0), (4, 3), (0, 3)];
+        assert_eq!(double_area(&rectangle), 24);
+        assert_eq!(perimeter(&rectangle), 14.0);
+        assert_eq!(boundary_points(&rectangle), 14);
+        assert_eq!(interior_points(&rectangle), 3 * 2);
+
+        // The other way around gives the same results.
+        let mut reversed = rectangle;
+        reversed.reverse();
+        assert_eq!(double_area(&reversed), 24);
+        assert_eq!(interior_points(&reversed), 6);
+    }
+
+    #[test]
+    fn measures_triangle_with_diagonal() {
+        let triangle: [(i32, i32); 3] = [(0, 0), (4, 0), (0, 4)];
+        assert_eq!(double_area(&triangle), 16);
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+        assert!((perimeter(&triangle) - (8.0 + 32f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn handles_large_coordinates() {
+        let big = 1_i128 << 62;
+        let square = [(0, 0), (big, 0), (big, big), (0, big)];
+        assert_eq!(double_area(&square), 2 * big * big);
+        assert_eq!(boundary_points(&square), 4 * big);
+    }
+}
@@ -0,0 +1,24 @@
+//! Advent of Code repo for 2023
+//!
+//! Every day is a public module with a `Solver` implementation, see `solver::registry`.
+
+pub mod bench;
+pub mod cli;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day07;
+pub mod day09;
+pub mod day10;
+pub mod day12;
+pub mod day13;
+pub mod error;
+pub mod geometry;
+pub mod logging;
+pub mod nonogram;
+pub mod output;
+pub mod point;
+pub mod solver;
+pub mod utils;
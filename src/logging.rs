@@ -0,0 +1,111 @@
+//! Logger for the debug output of the solvers, filtered by day and level.
+//!
+//! Solvers log with the `log` macros. The day of a message is taken from its
+//! target, which is the module path like `aoc2023::day07` or an explicit `day07`.
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Which levels are shown, globally and for single days.
+///
+/// Parsed from a spec like `warn,12=debug,5=trace`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSpec {
+    default: LevelFilter,
+    days: Vec<(u8, LevelFilter)>,
+}
+
+impl Default for LogSpec {
+    fn default() -> Self {
+        Self {
+            default: LevelFilter::Warn,
+            days: Vec::new(),
+        }
+    }
+}
+
+impl LogSpec {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut result = Self::default();
+        for part in spec.split(',') {
+            let invalid = || format!("Invalid log spec: {}", part);
+            match part.split_once('=') {
+                Some((day, level)) => {
+                    let day = day.parse().map_err(|_| invalid())?;
+                    let level = level.parse().map_err(|_| invalid())?;
+                    result.days.push((day, level));
+                }
+                None => result.default = part.parse().map_err(|_| invalid())?,
+            }
+        }
+        Ok(result)
+    }
+
+    fn level_for(&self, day: Option<u8>) -> LevelFilter {
+        self.days
+            .iter()
+            .rev()
+            .find(|(d, _)| Some(*d) == day)
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.days
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+struct Logger {
+    spec: LogSpec,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.spec.level_for(day_of(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the logger. Only the first call has an effect.
+pub fn init(spec: &LogSpec) {
+    let logger = Logger { spec: spec.clone() };
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(spec.max_level());
+    }
+}
+
+/// The day of a log target like `aoc2023::day07`.
+fn day_of(target: &str) -> Option<u8> {
+    let module = target.rsplit("::").next()?;
+    module.strip_prefix("day")?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_spec() {
+        let spec = LogSpec::parse("info,12=trace,5=off").unwrap();
+        assert_eq!(spec.level_for(None), LevelFilter::Info);
+        assert_eq!(spec.level_for(Some(12)), LevelFilter::Trace);
+        assert_eq!(spec.level_for(Some(5)), LevelFilter::Off);
+        assert_eq!(spec.max_level(), LevelFilter::Trace);
+        assert!(LogSpec::parse("12=loud").is_err());
+    }
+
+    #[test]
+    fn finds_day_in_target() {
+        assert_eq!(day_of("aoc2023::day07"), Some(7));
+        assert_eq!(day_of("day12"), Some(12));
+        assert_eq!(day_of("aoc2023::cli"), None);
+    }
+}
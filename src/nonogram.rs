@@ -0,0 +1,167 @@
+//! 2D nonogram solver, where every row and column is a Day 12 `HotSpringRow`.
+//!
+//! Lines are solved one at a time with `HotSpringRow::forced_cells` until nothing
+//! changes. If cells are still unknown after that, the solver guesses one and backtracks.
+
+use crate::day12::{render_state, verify_solution, HotSpringRow, HotSpringState};
+
+/// Run-length clues for each row (top to bottom) and each column (left to right).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonogram {
+    pub rows: Vec<Vec<u8>>,
+    pub columns: Vec<Vec<u8>>,
+}
+
+/// The result of solving a nonogram.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    None,
+    Unique(Vec<Vec<HotSpringState>>),
+    /// At least two solutions, the first two that were found.
+    Multiple(Vec<Vec<HotSpringState>>, Vec<Vec<HotSpringState>>),
+}
+
+impl Nonogram {
+    pub fn solve(&self) -> Solutions {
+        let grid = vec![vec![HotSpringState::Unknown; self.columns.len()]; self.rows.len()];
+        let mut found = Vec::new();
+        self.search(grid, &mut found);
+        let mut found = found.into_iter();
+        match (found.next(), found.next()) {
+            (None, _) => Solutions::None,
+            (Some(solution), None) => Solutions::Unique(solution),
+            (Some(first), Some(second)) => Solutions::Multiple(first, second),
+        }
+    }
+
+    /// Collects solutions into `found` until there are two of them.
+    fn search(
+        &self,
+        mut grid: Vec<Vec<HotSpringState>>,
+        found: &mut Vec<Vec<Vec<HotSpringState>>>,
+    ) {
+        if !self.propagate(&mut grid) {
+            return;
+        }
+        let unknown = grid.iter().enumerate().find_map(|(y, row)| {
+            let x = row
+                .iter()
+                .position(|cell| *cell == HotSpringState::Unknown)?;
+            Some((x, y))
+        });
+        let Some((x, y)) = unknown else {
+            debug_assert!(self.lines(&grid).all(|line| verify_solution(&line)));
+            found.push(grid);
+            return;
+        };
+        for guess in [HotSpringState::Damaged, HotSpringState::Operational] {
+            if found.len() >= 2 {
+                return;
+            }
+            let mut guessed = grid.clone();
+            guessed[y][x] = guess;
+            self.search(guessed, found);
+        }
+    }
+
+    /// Line-solves rows and columns until nothing changes.
+    /// Returns false if some line has no valid arrangement.
+    fn propagate(&self, grid: &mut [Vec<HotSpringState>]) -> bool {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (y, clues) in self.rows.iter().enumerate() {
+                let line = grid[y].clone();
+                let Some(forced) = line_row(line, clues).forced_cells() else {
+                    return false;
+                };
+                for (x, cell) in forced.into_iter().enumerate() {
+                    if let Some(cell) = cell {
+                        changed |= grid[y][x] != cell;
+                        grid[y][x] = cell;
+                    }
+                }
+            }
+            for (x, clues) in self.columns.iter().enumerate() {
+                let line = grid.iter().map(|row| row[x]).collect();
+                let Some(forced) = line_row(line, clues).forced_cells() else {
+                    return false;
+                };
+                for (y, cell) in forced.into_iter().enumerate() {
+                    if let Some(cell) = cell {
+                        changed |= grid[y][x] != cell;
+                        grid[y][x] = cell;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// All rows and then all columns of the grid, together with their clues.
+    fn lines<'a>(
+        &'a self,
+        grid: &'a [Vec<HotSpringState>],
+    ) -> impl Iterator<Item = HotSpringRow> + 'a {
+        let rows = (self.rows.iter().zip(grid)).map(|(clues, row)| line_row(row.clone(), clues));
+        let columns = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(x, clues)| line_row(grid.iter().map(|row| row[x]).collect(), clues));
+        rows.chain(columns)
+    }
+}
+
+fn line_row(state: Vec<HotSpringState>, clues: &[u8]) -> HotSpringRow {
+    HotSpringRow {
+        state,
+        contiguous_broken: clues.to_vec(),
+    }
+}
+
+/// Renders a grid with one line per row, using the Day 12 characters.
+pub fn render_grid(grid: &[Vec<HotSpringState>]) -> String {
+    grid.iter()
+        .map(|row| render_state(row))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nonogram(rows: &[&[u8]], columns: &[&[u8]]) -> Nonogram {
+        Nonogram {
+            rows: rows.iter().map(|clues| clues.to_vec()).collect(),
+            columns: columns.iter().map(|clues| clues.to_vec()).collect(),
+        }
+    }
+
+    #[test]
+    fn solves_unique_puzzle() {
+        // A small heart.
+        let puzzle = nonogram(
+            &[&[1, 1], &[5], &[5], &[3], &[1]],
+            &[&[2], &[4], &[4], &[4], &[2]],
+        );
+        let Solutions::Unique(solution) = puzzle.solve() else {
+            panic!("Expected a unique solution");
+        };
+        assert_eq!(render_grid(&solution), ".#.#.\n#####\n#####\n.###.\n..#..");
+    }
+
+    #[test]
+    fn detects_ambiguous_and_impossible_puzzles() {
+        let ambiguous = nonogram(&[&[1], &[1]], &[&[1], &[1]]);
+        let Solutions::Multiple(first, second) = ambiguous.solve() else {
+            panic!("Expected multiple solutions");
+        };
+        assert_eq!(render_grid(&first), "#.\n.#");
+        assert_eq!(render_grid(&second), ".#\n#.");
+
+        let impossible = nonogram(&[&[2], &[]], &[&[1], &[]]);
+        assert_eq!(impossible.solve(), Solutions::None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Output formats for the results of the `run` command.
+
+use std::time::Duration;
+
+/// How results are printed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable lines like "Day 05 part 2: 46".
+    #[default]
+    Text,
+    /// One JSON object per line, for tools that aggregate results.
+    Json,
+}
+
+impl OutputFormat {
+    pub fn parse(format: &str) -> Result<Self, String> {
+        match format {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Invalid output format: {}", format)),
+        }
+    }
+}
+
+/// The answer for one part of one day.
+#[derive(Debug)]
+pub struct PartResult<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    /// Name of the input, like "day05_test.txt" or "-" for stdin.
+    pub input: &'a str,
+    /// Time spent in the part, without parsing.
+    pub elapsed: Duration,
+}
+
+impl PartResult<'_> {
+    pub fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => {
+                format!("Day {:02} part {}: {}", self.day, self.part, self.answer)
+            }
+            OutputFormat::Json => format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"input\":{},\"elapsed_ns\":{}}}",
+                self.day,
+                self.part,
+                json_string(self.answer),
+                json_string(self.input),
+                self.elapsed.as_nanos()
+            ),
+        }
+    }
+}
+
+/// A day or part that could not be solved, as a JSON line.
+/// The part is left out if the whole day failed, e.g. while parsing.
+pub fn json_error(day: u8, part: Option<u8>, input: &str, error: &str) -> String {
+    let part = part.map_or(String::new(), |part| format!("\"part\":{},", part));
+    format!(
+        "{{\"day\":{},{}\"input\":{},\"error\":{}}}",
+        day,
+        part,
+        json_string(input),
+        json_string(error)
+    )
+}
+
+/// Quotes and escapes a string for JSON.
+fn json_string(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_json_line() {
+        let result = PartResult {
+            day: 5,
+            part: 2,
+            answer: "46",
+            input: "day05_test.txt",
+            elapsed: Duration::from_micros(12),
+        };
+        assert_eq!(result.format(OutputFormat::Text), "Day 05 part 2: 46");
+        assert_eq!(
+            result.format(OutputFormat::Json),
+            r#"{"day":5,"part":2,"answer":"46","input":"day05_test.txt","elapsed_ns":12000}"#
+        );
+    }
+
+    #[test]
+    fn formats_json_errors() {
+        assert_eq!(
+            json_error(1, Some(1), "day01_test.txt", "Broken"),
+            r#"{"day":1,"part":1,"input":"day01_test.txt","error":"Broken"}"#
+        );
+        assert_eq!(
+            json_error(1, None, "-", "Invalid"),
+            r#"{"day":1,"input":"-","error":"Invalid"}"#
+        );
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+    }
+}
@@ -0,0 +1,346 @@
+//! Points, vectors and directions on a grid.
+//!
+//! Coordinates are `(x, y)` with x growing to the right and y growing down,
+//! like the lines and characters of the puzzle input.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// The difference between two points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> u32 {
+        (other - self).manhattan_length()
+    }
+
+    /// The number of king moves between the points.
+    pub fn chebyshev_distance(self, other: Point) -> u32 {
+        (other - self).chebyshev_length()
+    }
+
+    /// The four points next to this one.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+
+    /// The eight points around this one, including the diagonals.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+}
+
+impl Vector {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_length(self) -> u32 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev_length(self) -> u32 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Point> for (i32, i32) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<(i32, i32)> for Vector {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, other: Vector) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, other: Vector) -> Point {
+        self + -other
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        self + -other
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: i32) -> Vector {
+        Vector::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// One of the four directions along the grid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// A step of length one in this direction.
+    pub fn vector(self) -> Vector {
+        use Direction::*;
+        match self {
+            Up => Vector::new(0, -1),
+            Down => Vector::new(0, 1),
+            Left => Vector::new(-1, 0),
+            Right => Vector::new(1, 0),
+        }
+    }
+
+    /// Turns a quarter counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        use Direction::*;
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    /// Turns a quarter clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().reverse()
+    }
+
+    pub fn reverse(self) -> Direction {
+        use Direction::*;
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+}
+
+/// One of the eight directions to the points around a point.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting with `Up`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// A step in this direction, diagonal steps move along both axes.
+    pub fn vector(self) -> Vector {
+        use Direction8::*;
+        match self {
+            Up => Vector::new(0, -1),
+            UpRight => Vector::new(1, -1),
+            Right => Vector::new(1, 0),
+            DownRight => Vector::new(1, 1),
+            Down => Vector::new(0, 1),
+            DownLeft => Vector::new(-1, 1),
+            Left => Vector::new(-1, 0),
+            UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// Turns an eighth counterclockwise.
+    pub fn turn_left(self) -> Direction8 {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turns an eighth clockwise.
+    pub fn turn_right(self) -> Direction8 {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Self {
+        direction.vector()
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.vector()
+    }
+}
+
+impl Add<Direction8> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction8) -> Point {
+        self + direction.vector()
+    }
+}
+
+impl Add<Direction> for (i32, i32) {
+    type Output = (i32, i32);
+
+    fn add(self, direction: Direction) -> (i32, i32) {
+        (Point::from(self) + direction).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_left().turn_left(), direction.reverse());
+            assert_eq!(-direction.vector(), direction.reverse().vector());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownLeft.reverse(), Direction8::UpRight);
+        assert!(Direction8::DownLeft.is_diagonal());
+        assert!(!Direction8::from(Direction::Left).is_diagonal());
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::from((1, 2));
+        let b = Point::new(-3, 5);
+        assert_eq!(b - a, Vector::new(-4, 3));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(<(i32, i32)>::from(a + Direction8::DownLeft), (0, 3));
+        assert_eq!((1, 2) + Direction::Up, (1, 1));
+        assert_eq!(
+            a.neighbours8()
+                .filter(|n| a.chebyshev_distance(*n) == 1)
+                .count(),
+            8
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! The `Solver` trait implemented by every day, and the registry of all days.
+
+use std::{any::Any, fmt::Display, marker::PhantomData};
+
+use crate::error::ParseError;
+
+/// A solution for a single day of the Advent of Code.
+///
+/// The input is parsed once and then shared by both parts.
+pub trait Solver {
+    /// The parsed representation of the puzzle input.
+    type Input: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Parses the puzzle input and solves part 1.
+    fn solve_part1(input: &str) -> Result<Self::Answer1, ParseError> {
+        Ok(Self::part1(&Self::parse(input)?))
+    }
+
+    /// Parses the puzzle input and solves part 2.
+    fn solve_part2(input: &str) -> Result<Self::Answer2, ParseError> {
+        Ok(Self::part2(&Self::parse(input)?))
+    }
+}
+
+/// Type erased version of a `Solver`, so all days can be stored in one registry.
+pub trait DynSolver {
+    fn day(&self) -> u8;
+    /// File name of the example input for `part` in the input directory.
+    fn example(&self, part: u8) -> String;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, input: &dyn Any) -> String;
+    fn part2(&self, input: &dyn Any) -> String;
+}
+
+struct Registered<S> {
+    day: u8,
+    /// Example input for part 1, if it differs from the usual `dayNN_test.txt`.
+    part1_example: Option<&'static str>,
+    solver: PhantomData<S>,
+}
+
+impl<S: Solver> DynSolver for Registered<S> {
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn example(&self, part: u8) -> String {
+        match self.part1_example {
+            Some(example) if part == 1 => example.to_string(),
+            _ => format!("day{:02}_test.txt", self.day),
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> String {
+        S::part1(downcast::<S>(input)).to_string()
+    }
+
+    fn part2(&self, input: &dyn Any) -> String {
+        S::part2(downcast::<S>(input)).to_string()
+    }
+}
+
+fn downcast<S: Solver>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref::<S::Input>()
+        .expect("Input was not parsed by the same solver")
+}
+
+fn register<S: Solver + 'static>(day: u8) -> Box<dyn DynSolver> {
+    Box::new(Registered::<S> {
+        day,
+        part1_example: None,
+        solver: PhantomData,
+    })
+}
+
+/// Registers a day whose parts have different examples, like Day 1 where the
+/// part 2 example has lines without any digit.
+fn register_with_part1_example<S: Solver + 'static>(
+    day: u8,
+    part1_example: &'static str,
+) -> Box<dyn DynSolver> {
+    Box::new(Registered::<S> {
+        day,
+        part1_example: Some(part1_example),
+        solver: PhantomData,
+    })
+}
+
+/// All days that have a solver, ordered by day.
+pub fn registry() -> Vec<Box<dyn DynSolver>> {
+    vec![
+        register_with_part1_example::<crate::day01::Day01>(1, "day01_test_part1.txt"),
+        register::<crate::day02::Day02>(2),
+        register::<crate::day03::Day03>(3),
+        register::<crate::day04::Day04>(4),
+        register::<crate::day05::Day05>(5),
+        register::<crate::day07::Day07>(7),
+        register::<crate::day09::Day09>(9),
+        register::<crate::day10::Day10>(10),
+        register::<crate::day12::Day12>(12),
+        register::<crate::day13::Day13>(13),
+    ]
+}
+
+/// Looks up the solver for a single day.
+pub fn find(day: u8) -> Option<Box<dyn DynSolver>> {
+    registry().into_iter().find(|solver| solver.day() == day)
+}
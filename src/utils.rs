@@ -1,11 +1,346 @@
 //! Shared utility functions
 
-use std::{fs::File, io::Read};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
-/// Read a file to a string.
-pub fn read_file(filename: &str) -> String {
-    let mut input = String::new();
-    let mut file = File::open(format!("input/{}", filename)).unwrap();
-    file.read_to_string(&mut input).unwrap();
+use crate::{
+    error::{input_lines, InputLine, ParseError},
+    point::Point,
+};
+
+/// Environment variable that overrides the directory holding the input files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// An input file that could not be read.
+#[derive(Debug)]
+pub struct InputError {
+    /// The day the input was meant for, if known.
+    pub day: Option<u8>,
+    pub path: PathBuf,
+    pub source: std::io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.day {
+            Some(day) => write!(f, "Missing input for day {}: could not read ", day)?,
+            None => write!(f, "Could not read ")?,
+        }
+        write!(f, "{}: {}", self.path.display(), self.source)
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// The directory holding the input files.
+///
+/// This is `$AOC_INPUT_DIR` if set, otherwise the `input` directory of the crate,
+/// so it does not depend on the current working directory.
+pub fn input_dir() -> PathBuf {
+    match std::env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("input"),
+    }
+}
+
+/// Read a file from the input directory to a string.
+pub fn read_file(filename: &str) -> Result<String, InputError> {
+    read_path(&input_dir().join(filename))
+}
+
+/// Read the input of a day, either the full input or the example input.
+pub fn read_day(day: u8, test: bool) -> Result<String, InputError> {
+    let suffix = if test { "_test" } else { "" };
+    read_day_file(day, &format!("day{:02}{}.txt", day, suffix))
+}
+
+/// Read a file from the input directory that holds an input of the given day.
+pub fn read_day_file(day: u8, filename: &str) -> Result<String, InputError> {
+    read_file(filename).map_err(|error| InputError {
+        day: Some(day),
+        ..error
+    })
+}
+
+/// Read any file to a normalized string.
+pub fn read_path(path: &Path) -> Result<String, InputError> {
+    match std::fs::read_to_string(path) {
+        Ok(input) => Ok(normalize(&input)),
+        Err(source) => Err(InputError {
+            day: None,
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// Converts CRLF line endings to LF and removes trailing newlines.
+pub fn normalize(input: &str) -> String {
     input
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// Positions are `(x, y)` with x growing to the right and y growing down.
+/// They are signed, so positions next to the border can be looked up and are simply outside.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let height = rows.len();
+        let cells = rows.into_iter().flatten().collect();
+        Some(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one cell per character. `cell` returns None for invalid characters.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_lines(input_lines(day, input), cell)
+    }
+
+    /// Like `parse`, for lines that are only a part of the input.
+    pub fn parse_lines<'a>(
+        lines: impl IntoIterator<Item = InputLine<'a>>,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = Vec::new();
+        for line in lines {
+            let mut row = Vec::with_capacity(line.text.len());
+            for (x, char) in line.text.chars().enumerate() {
+                match cell(char) {
+                    Some(value) => row.push(value),
+                    None => return Err(line.error_at(x, "Invalid character")),
+                }
+            }
+            if rows
+                .first()
+                .is_some_and(|first: &Vec<T>| first.len() != row.len())
+            {
+                return Err(line.error(line.text, "Row width differs from the rows above"));
+            }
+            rows.push(row);
+        }
+        Ok(Self::from_rows(rows).expect("Row widths are checked"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, (x, y): (i32, i32)) -> Option<usize> {
+        let x = usize::try_from(x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    pub fn contains(&self, position: (i32, i32)) -> bool {
+        self.index(position).is_some()
+    }
+
+    /// The cell at `position`, or None if it is outside the grid.
+    pub fn get(&self, position: (i32, i32)) -> Option<&T> {
+        self.index(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: (i32, i32)) -> Option<&mut T> {
+        self.index(position).map(|index| &mut self.cells[index])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (i32, i32)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x as i32, y as i32)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((i32, i32), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The neighbours above, below, left and right of `position` that are inside the grid.
+    pub fn neighbours4(&self, position: (i32, i32)) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.neighbours(Point::from(position).neighbours4())
+    }
+
+    /// Like `neighbours4`, including the diagonals, clockwise starting above.
+    pub fn neighbours8(&self, position: (i32, i32)) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.neighbours(Point::from(position).neighbours8())
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        points: impl Iterator<Item = Point> + 'a,
+    ) -> impl Iterator<Item = (i32, i32)> + 'a {
+        points
+            .map(<(i32, i32)>::from)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// The cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, but then there are no cells to split.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self
+                .columns()
+                .flat_map(|column| column.collect::<Vec<_>>().into_iter().rev())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|x| self.column(x))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalize("a\n\nb\n"), "a\n\nb");
+    }
+
+    #[test]
+    fn reports_missing_day() {
+        let error = read_day(99, true).unwrap_err();
+        assert_eq!(error.day, Some(99));
+        assert!(error.to_string().starts_with("Missing input for day 99: "));
+        assert!(error.path.ends_with("day99_test.txt"));
+    }
+
+    fn sample_grid() -> Grid<char> {
+        Grid::parse(0, "abc\ndef", Some).unwrap()
+    }
+
+    fn render(grid: &Grid<char>) -> Vec<String> {
+        grid.rows().map(|row| row.iter().collect()).collect()
+    }
+
+    #[test]
+    fn parses_grid() {
+        let grid = sample_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 1)), None);
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+
+        let error = Grid::parse(3, "ab\nabc", Some).unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "Row width differs from the rows above")
+        );
+        let error = Grid::parse(3, "ab\na?", |c| c.is_alphabetic().then_some(c)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn finds_neighbours_inside_grid() {
+        let grid = sample_grid();
+        let neighbours4: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(neighbours4, [(0, 1), (1, 0)]);
+        let neighbours8: Vec<_> = grid.neighbours8((1, 1)).collect();
+        assert_eq!(neighbours8, [(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = sample_grid();
+        assert_eq!(render(&grid.transpose()), ["ad", "be", "cf"]);
+        assert_eq!(render(&grid.rotate_clockwise()), ["da", "eb", "fc"]);
+        assert_eq!(render(&grid.rotate_counterclockwise()), ["cf", "be", "ad"]);
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
 }
@@ -0,0 +1,74 @@
+//! Regression tests for all registered days against the known answers in `answers.txt`.
+
+use aoc2023::{solver, utils};
+
+/// One line of `answers.txt`.
+#[derive(Debug)]
+struct ExpectedAnswer {
+    day: u8,
+    part: u8,
+    input: String,
+    answer: String,
+}
+
+fn load_answers() -> Vec<ExpectedAnswer> {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt");
+    let text = std::fs::read_to_string(path).expect("answers.txt is checked in");
+
+    let mut answers = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let invalid = || -> ! { panic!("Invalid line {} in answers.txt: {}", i + 1, line) };
+        if columns.len() != 4 {
+            invalid();
+        }
+        answers.push(ExpectedAnswer {
+            day: columns[0].parse().unwrap_or_else(|_| invalid()),
+            part: match columns[1] {
+                "1" => 1,
+                "2" => 2,
+                _ => invalid(),
+            },
+            input: columns[2].to_string(),
+            answer: columns[3].to_string(),
+        });
+    }
+    answers
+}
+
+/// Runs every answer and returns a description of each mismatch.
+fn check_answers() -> Vec<String> {
+    let mut failures = Vec::new();
+    for expected in load_answers() {
+        let Some(solver) = solver::find(expected.day) else {
+            failures.push(format!("No solver for day {}", expected.day));
+            continue;
+        };
+        let result = utils::read_file(&expected.input)
+            .map_err(|error| error.to_string())
+            .and_then(|input| solver.parse(&input).map_err(|error| error.to_string()))
+            .map(|parsed| match expected.part {
+                1 => solver.part1(parsed.as_ref()),
+                _ => solver.part2(parsed.as_ref()),
+            });
+        match result {
+            Ok(answer) if answer == expected.answer => {}
+            Ok(answer) => failures.push(format!(
+                "Day {} part {} on {}: expected {}, got {}",
+                expected.day, expected.part, expected.input, expected.answer, answer
+            )),
+            Err(error) => failures.push(error),
+        }
+    }
+    failures
+}
+
+#[test]
+fn all_answers_match() {
+    let failures = check_answers();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}